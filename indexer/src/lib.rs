@@ -0,0 +1,318 @@
+use {
+    biodex::events::{decode_logs, BiodexEvent},
+    rusqlite::{params, Connection, OptionalExtension},
+    serde::Deserialize,
+    solana_program::pubkey::Pubkey,
+    std::path::Path,
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        signature TEXT NOT NULL,
+        event_index INTEGER NOT NULL,
+        slot INTEGER NOT NULL,
+        PRIMARY KEY (signature, event_index)
+    );
+    CREATE TABLE IF NOT EXISTS users (
+        wallet TEXT PRIMARY KEY,
+        first_discoveries INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS plants (
+        name TEXT PRIMARY KEY,
+        first_discoverer TEXT,
+        new_species INTEGER,
+        discovered_slot INTEGER,
+        minted INTEGER NOT NULL DEFAULT 0,
+        burned INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS cards (
+        owner TEXT NOT NULL,
+        plant_name TEXT NOT NULL,
+        rarity TEXT NOT NULL,
+        mint TEXT NOT NULL,
+        quiz_id INTEGER,
+        slot INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS cards_by_owner ON cards (owner);
+";
+
+// One entry of a JSON dump; the fields mirror `getTransaction` (signature, slot,
+// meta.err and meta.logMessages) flattened into a single object.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransactionLogs {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(default)]
+    pub err: Option<serde_json::Value>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub owner: String,
+    pub plant_name: String,
+    pub rarity: String,
+    pub mint: String,
+    pub quiz_id: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plant {
+    pub name: String,
+    pub first_discoverer: Option<String>,
+    pub new_species: Option<bool>,
+    pub minted: u64,
+    pub burned: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Discoverer {
+    pub wallet: String,
+    pub first_discoveries: u64,
+}
+
+pub struct Indexer {
+    conn: Connection,
+}
+
+impl Indexer {
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn ingest_json(
+        &mut self,
+        program_id: &Pubkey,
+        json: &str,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let transactions: Vec<TransactionLogs> = serde_json::from_str(json)?;
+        let mut applied = 0;
+        for transaction in &transactions {
+            applied += self.ingest_transaction(program_id, transaction)?;
+        }
+        Ok(applied)
+    }
+
+    // Failed transactions are skipped and already-seen events are ignored, so the same
+    // dump can be replayed safely. Only events logged by `program_id` are applied.
+    // Returns the number of newly applied events.
+    pub fn ingest_transaction(
+        &mut self,
+        program_id: &Pubkey,
+        transaction: &TransactionLogs,
+    ) -> rusqlite::Result<usize> {
+        if transaction.err.as_ref().is_some_and(|err| !err.is_null()) {
+            return Ok(0);
+        }
+
+        let db = self.conn.transaction()?;
+        let mut applied = 0;
+        for (event_index, event) in decode_logs(program_id, &transaction.logs)
+            .into_iter()
+            .enumerate()
+        {
+            let inserted = db.execute(
+                "INSERT OR IGNORE INTO events (signature, event_index, slot) VALUES (?1, ?2, ?3)",
+                params![
+                    transaction.signature,
+                    event_index as i64,
+                    transaction.slot as i64
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+
+            apply_event(&db, &event, transaction.slot)?;
+            applied += 1;
+        }
+        db.commit()?;
+
+        Ok(applied)
+    }
+
+    pub fn cards_for_user(&self, wallet: &str) -> rusqlite::Result<Vec<Card>> {
+        let mut statement = self.conn.prepare(
+            "SELECT owner, plant_name, rarity, mint, quiz_id FROM cards
+             WHERE owner = ?1 ORDER BY slot, rowid",
+        )?;
+        let cards = statement
+            .query_map(params![wallet], |row| {
+                Ok(Card {
+                    owner: row.get(0)?,
+                    plant_name: row.get(1)?,
+                    rarity: row.get(2)?,
+                    mint: row.get(3)?,
+                    quiz_id: row.get::<_, Option<i64>>(4)?.map(|quiz_id| quiz_id as u64),
+                })
+            })?
+            .collect();
+        cards
+    }
+
+    pub fn plants_for_user(&self, wallet: &str) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.conn.prepare(
+            "SELECT DISTINCT plant_name FROM cards WHERE owner = ?1 ORDER BY plant_name",
+        )?;
+        let plants = statement
+            .query_map(params![wallet], |row| row.get(0))?
+            .collect();
+        plants
+    }
+
+    pub fn plant(&self, name: &str) -> rusqlite::Result<Option<Plant>> {
+        self.conn
+            .query_row(
+                "SELECT name, first_discoverer, new_species, minted, burned FROM plants
+                 WHERE name = ?1",
+                params![name],
+                |row| {
+                    Ok(Plant {
+                        name: row.get(0)?,
+                        first_discoverer: row.get(1)?,
+                        new_species: row.get(2)?,
+                        minted: row.get::<_, i64>(3)? as u64,
+                        burned: row.get::<_, i64>(4)? as u64,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    pub fn discoverer_leaderboard(&self, limit: usize) -> rusqlite::Result<Vec<Discoverer>> {
+        let mut statement = self.conn.prepare(
+            "SELECT wallet, first_discoveries FROM users WHERE first_discoveries > 0
+             ORDER BY first_discoveries DESC, wallet LIMIT ?1",
+        )?;
+        let leaderboard = statement
+            .query_map(params![limit as i64], |row| {
+                Ok(Discoverer {
+                    wallet: row.get(0)?,
+                    first_discoveries: row.get::<_, i64>(1)? as u64,
+                })
+            })?
+            .collect();
+        leaderboard
+    }
+}
+
+fn apply_event(db: &Connection, event: &BiodexEvent, slot: u64) -> rusqlite::Result<()> {
+    match event {
+        BiodexEvent::CardMinted {
+            owner,
+            plant_name,
+            rarity,
+            mint,
+            quiz_id,
+        } => {
+            db.execute(
+                "INSERT INTO cards (owner, plant_name, rarity, mint, quiz_id, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    owner.to_string(),
+                    plant_name,
+                    format!("{:?}", rarity),
+                    mint.to_string(),
+                    quiz_id.map(|quiz_id| quiz_id as i64),
+                    slot as i64
+                ],
+            )?;
+            db.execute(
+                "INSERT INTO plants (name, minted) VALUES (?1, 1)
+                 ON CONFLICT (name) DO UPDATE SET minted = minted + 1",
+                params![plant_name],
+            )?;
+            db.execute(
+                "INSERT OR IGNORE INTO users (wallet) VALUES (?1)",
+                params![owner.to_string()],
+            )?;
+        }
+        BiodexEvent::PlantFirstDiscovered {
+            plant_name,
+            discoverer,
+            new_species,
+        } => {
+            db.execute(
+                "INSERT INTO plants (name, first_discoverer, new_species, discovered_slot)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (name) DO UPDATE SET first_discoverer = ?2, new_species = ?3,
+                 discovered_slot = ?4",
+                params![plant_name, discoverer.to_string(), new_species, slot as i64],
+            )?;
+            db.execute(
+                "INSERT INTO users (wallet, first_discoveries) VALUES (?1, 1)
+                 ON CONFLICT (wallet) DO UPDATE SET first_discoveries = first_discoveries + 1",
+                params![discoverer.to_string()],
+            )?;
+        }
+        // The matching CardMinted event carries everything the collection needs.
+        BiodexEvent::QuizCardAwarded { .. } => {}
+        BiodexEvent::CardBurned {
+            owner,
+            plant_name,
+            rarity,
+            mint,
+        } => {
+            db.execute(
+                "DELETE FROM cards WHERE rowid = (
+                     SELECT rowid FROM cards
+                     WHERE owner = ?1 AND plant_name = ?2 AND rarity = ?3 AND mint = ?4
+                     ORDER BY slot LIMIT 1
+                 )",
+                params![
+                    owner.to_string(),
+                    plant_name,
+                    format!("{:?}", rarity),
+                    mint.to_string()
+                ],
+            )?;
+            db.execute(
+                "UPDATE plants SET burned = burned + 1 WHERE name = ?1",
+                params![plant_name],
+            )?;
+        }
+        BiodexEvent::CardTransferred {
+            from,
+            to,
+            plant_name,
+            rarity,
+            mint,
+        } => {
+            db.execute(
+                "UPDATE cards SET owner = ?2 WHERE rowid = (
+                     SELECT rowid FROM cards
+                     WHERE owner = ?1 AND plant_name = ?3 AND rarity = ?4 AND mint = ?5
+                     ORDER BY slot LIMIT 1
+                 )",
+                params![
+                    from.to_string(),
+                    to.to_string(),
+                    plant_name,
+                    format!("{:?}", rarity),
+                    mint.to_string()
+                ],
+            )?;
+            db.execute(
+                "INSERT OR IGNORE INTO users (wallet) VALUES (?1)",
+                params![to.to_string()],
+            )?;
+        }
+        // Set rewards are not plant cards, so they stay out of the collection tables.
+        BiodexEvent::SetRewardClaimed { .. } => {}
+        // The matching CardMinted event already records the RegionalRelic.
+        BiodexEvent::RegionFirstDiscovered { .. } => {}
+        // Leaves only matter for ownership proofs, which the indexer does not serve.
+        BiodexEvent::OwnershipLeafAppended { .. } => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use {biodex_indexer::Indexer, solana_program::pubkey::Pubkey, std::process::exit};
+
+const USAGE: &str = "usage:
+  biodex-indexer <db> ingest <program-id> <dump.json>
+  biodex-indexer <db> collection <wallet>
+  biodex-indexer <db> plant <name>
+  biodex-indexer <db> leaderboard [limit]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (db_path, command) = match args {
+        [db_path, command, ..] => (db_path, command.as_str()),
+        _ => return Err(USAGE.into()),
+    };
+    let mut indexer = Indexer::open(db_path)?;
+
+    match (command, &args[2..]) {
+        ("ingest", [program_id, dump_path]) => {
+            let applied = indexer.ingest_json(
+                &program_id.parse::<Pubkey>()?,
+                &std::fs::read_to_string(dump_path)?,
+            )?;
+            println!("Applied {} new events", applied);
+        }
+        ("collection", [wallet]) => {
+            for card in indexer.cards_for_user(wallet)? {
+                match card.quiz_id {
+                    Some(quiz_id) => {
+                        println!("{}\t{}\tquiz {}", card.plant_name, card.rarity, quiz_id)
+                    }
+                    None => println!("{}\t{}", card.plant_name, card.rarity),
+                }
+            }
+        }
+        ("plant", [name]) => match indexer.plant(name)? {
+            Some(plant) => println!(
+                "{}\tfirst discoverer: {}\tminted: {}\tburned: {}",
+                plant.name,
+                plant.first_discoverer.as_deref().unwrap_or("-"),
+                plant.minted,
+                plant.burned
+            ),
+            None => println!("{} has not been indexed", name),
+        },
+        ("leaderboard", rest) => {
+            let limit = match rest {
+                [limit] => limit.parse()?,
+                [] => 10,
+                _ => return Err(USAGE.into()),
+            };
+            for (rank, discoverer) in indexer.discoverer_leaderboard(limit)?.iter().enumerate() {
+                println!(
+                    "{}.\t{}\t{}",
+                    rank + 1,
+                    discoverer.wallet,
+                    discoverer.first_discoveries
+                );
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
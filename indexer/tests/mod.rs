@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use biodex_indexer::{Card, Discoverer, Indexer};
+    use solana_program::pubkey::Pubkey;
+
+    const FIXTURE: &str = include_str!("fixtures/transactions.json");
+
+    fn program_id() -> Pubkey {
+        "cGfHiC6Kgg3FpFZvgwGcswsCRtp4aBP2fzuXRQPizuN"
+            .parse()
+            .unwrap()
+    }
+
+    fn wallet(seed: u8) -> String {
+        Pubkey::new_from_array([seed; 32]).to_string()
+    }
+
+    fn card(owner: u8, plant_name: &str, rarity: &str, mint: u8, quiz_id: Option<u64>) -> Card {
+        Card {
+            owner: wallet(owner),
+            plant_name: plant_name.to_string(),
+            rarity: rarity.to_string(),
+            mint: wallet(mint),
+            quiz_id,
+        }
+    }
+
+    const ALICE: u8 = 1;
+    const BOB: u8 = 2;
+    const CAROL: u8 = 3;
+    const PRIMORDIAL_MINT: u8 = 11;
+    const EPIC_MINT: u8 = 12;
+    const AURORA_MINT: u8 = 13;
+    const CODEX_MINT: u8 = 14;
+
+    #[test]
+    fn test_ingest_builds_collections() {
+        println!("\n🧪 TEST: Fixture transactions build user collections");
+
+        let mut indexer = Indexer::in_memory().unwrap();
+        let applied = indexer.ingest_json(&program_id(), FIXTURE).unwrap();
+        assert_eq!(
+            applied, 12,
+            "Failed and forged transactions must be skipped"
+        );
+
+        assert_eq!(
+            indexer.cards_for_user(&wallet(ALICE)).unwrap(),
+            vec![card(ALICE, "Lotus", "MythicCrest", EPIC_MINT, None)]
+        );
+        assert_eq!(
+            indexer.cards_for_user(&wallet(BOB)).unwrap(),
+            vec![
+                card(BOB, "Rose", "PrimordialRelic", PRIMORDIAL_MINT, None),
+                card(BOB, "Lotus", "AuroraSeed", AURORA_MINT, None),
+                card(BOB, "Fern", "AuroraSeed", AURORA_MINT, None),
+            ]
+        );
+        assert_eq!(
+            indexer.cards_for_user(&wallet(CAROL)).unwrap(),
+            vec![card(CAROL, "Rose", "CodexOfInsight", CODEX_MINT, Some(1))]
+        );
+
+        assert_eq!(
+            indexer.plants_for_user(&wallet(BOB)).unwrap(),
+            vec!["Fern", "Lotus", "Rose"]
+        );
+        assert!(indexer.plant("Tulip").unwrap().is_none());
+
+        println!("✅ Collections match the fixture");
+    }
+
+    #[test]
+    fn test_plants_and_discoverer_leaderboard() {
+        println!("\n🧪 TEST: Plant stats and first-discoverer leaderboard");
+
+        let mut indexer = Indexer::in_memory().unwrap();
+        indexer.ingest_json(&program_id(), FIXTURE).unwrap();
+
+        let rose = indexer.plant("Rose").unwrap().unwrap();
+        assert_eq!(rose.first_discoverer, Some(wallet(ALICE)));
+        assert_eq!(rose.new_species, Some(false));
+        assert_eq!(rose.minted, 3);
+        assert_eq!(rose.burned, 1);
+
+        let lotus = indexer.plant("Lotus").unwrap().unwrap();
+        assert_eq!(lotus.first_discoverer, Some(wallet(BOB)));
+        assert_eq!(lotus.new_species, Some(true));
+
+        assert_eq!(
+            indexer.discoverer_leaderboard(10).unwrap(),
+            vec![
+                Discoverer {
+                    wallet: wallet(BOB),
+                    first_discoveries: 2,
+                },
+                Discoverer {
+                    wallet: wallet(ALICE),
+                    first_discoveries: 1,
+                },
+            ]
+        );
+        assert_eq!(indexer.discoverer_leaderboard(1).unwrap().len(), 1);
+
+        println!("✅ Leaderboard ranks Bob ahead of Alice");
+    }
+
+    #[test]
+    fn test_replaying_a_dump_is_idempotent() {
+        println!("\n🧪 TEST: Re-ingesting the same dump changes nothing");
+
+        let mut indexer = Indexer::in_memory().unwrap();
+        indexer.ingest_json(&program_id(), FIXTURE).unwrap();
+        let cards_before = indexer.cards_for_user(&wallet(BOB)).unwrap();
+
+        assert_eq!(indexer.ingest_json(&program_id(), FIXTURE).unwrap(), 0);
+        assert_eq!(indexer.cards_for_user(&wallet(BOB)).unwrap(), cards_before);
+        assert_eq!(indexer.plant("Rose").unwrap().unwrap().minted, 3);
+
+        println!("✅ Replay applied no events");
+    }
+}
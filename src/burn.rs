@@ -0,0 +1,263 @@
+use {
+    crate::{
+        collection::{load_plant_id, remove_card},
+        events::{emit, BiodexEvent},
+        instruction::{
+            find_ownership_address, CardRarityInstruction, OwnershipRecord, PlantCounter,
+        },
+        mint::{check_mint_authority, check_token_program},
+        processor::Processor,
+        profile,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_token_2022::{
+        extension::StateWithExtensions, instruction as token_instruction,
+        state::Account as TokenAccount,
+    },
+};
+
+pub fn burn_card(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    card_type: CardRarityInstruction,
+    quiz_id: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let holder = next_account_info(accounts_iter)?;
+    let mint_account = next_account_info(accounts_iter)?;
+    let token_account = next_account_info(accounts_iter)?;
+    let ownership_account = next_account_info(accounts_iter)?;
+    let plant_counter_account = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let collection_page_account = next_account_info(accounts_iter)?;
+    let profile_account = next_account_info(accounts_iter)?;
+    // Mint authority PDA. Only needed for soulbound cards, whose token account stays frozen.
+    let mint_authority = accounts_iter.next();
+
+    if !holder.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    check_token_program(token_program)?;
+
+    let (ownership_pda, _) =
+        find_ownership_address(program_id, plant_name, holder.key, card_type, quiz_id);
+    if ownership_pda != *ownership_account.key || ownership_account.owner != program_id {
+        msg!("Ownership account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let record = OwnershipRecord::try_from_slice(&ownership_account.data.borrow())?;
+    if record.owner != *holder.key || record.nft_mint != *mint_account.key {
+        msg!("Ownership record does not belong to this holder and mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (plant_counter_pda, _) =
+        Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], program_id);
+    if plant_counter_pda != *plant_counter_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let frozen = StateWithExtensions::<TokenAccount>::unpack(&token_account.data.borrow())?
+        .base
+        .is_frozen();
+    let freeze_accounts = match (frozen, mint_authority) {
+        (false, _) => None,
+        (true, Some(mint_authority)) => Some((
+            [
+                token_account.clone(),
+                mint_account.clone(),
+                mint_authority.clone(),
+                token_program.clone(),
+            ],
+            check_mint_authority(program_id, mint_authority)?,
+        )),
+        (true, None) => {
+            msg!("Soulbound card is frozen; pass the mint authority PDA to burn it");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+    };
+
+    if let Some((freeze_accounts, mint_authority_bump)) = &freeze_accounts {
+        invoke_signed(
+            &token_instruction::thaw_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                freeze_accounts[2].key,
+                &[],
+            )?,
+            freeze_accounts,
+            &[&[b"mint_authority", &[*mint_authority_bump]]],
+        )?;
+    }
+
+    msg!("Burning {:?} card for plant {}", record.rarity, plant_name);
+    invoke(
+        &token_instruction::burn(
+            token_program.key,
+            token_account.key,
+            mint_account.key,
+            holder.key,
+            &[],
+            1,
+        )?,
+        &[
+            token_account.clone(),
+            mint_account.clone(),
+            holder.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Other soulbound cards of this mint may still sit in the same token account.
+    if let Some((freeze_accounts, mint_authority_bump)) = &freeze_accounts {
+        invoke_signed(
+            &token_instruction::freeze_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                freeze_accounts[2].key,
+                &[],
+            )?,
+            freeze_accounts,
+            &[&[b"mint_authority", &[*mint_authority_bump]]],
+        )?;
+    }
+
+    Processor::close_program_account(ownership_account, holder)?;
+
+    // Quiz cards are unique per quiz through their ownership PDA and are not in the bitmap.
+    if quiz_id.is_none() {
+        let plant_id = load_plant_id(program_id, plant_counter_account, plant_name)?;
+        remove_card(
+            program_id,
+            collection_page_account,
+            holder.key,
+            plant_id,
+            &record.rarity,
+        )?;
+    }
+    profile::remove_card(program_id, profile_account, holder.key, &record.rarity)?;
+
+    // Discovery cards always have a counter, since their plant id was loaded from it above.
+    // Quiz cards minted before SubmitQuizResult saved the counter may not; their burn cannot
+    // be counted against a plant that has no counter.
+    if plant_counter_account.data_is_empty() {
+        msg!("No plant counter for {}; burn is not counted", plant_name);
+    } else {
+        let mut counter = PlantCounter::try_from_slice(&plant_counter_account.data.borrow())?;
+        counter.burned_count += 1;
+
+        let serialized_counter = borsh::to_vec(&counter)?;
+        plant_counter_account.try_borrow_mut_data()?[..serialized_counter.len()]
+            .copy_from_slice(&serialized_counter);
+    }
+
+    emit(&BiodexEvent::CardBurned {
+        owner: *holder.key,
+        plant_name: plant_name.to_string(),
+        rarity: record.rarity,
+        mint: record.nft_mint,
+    });
+
+    Ok(())
+}
+
+// Shared by CloseOwnership and ReclaimRent. Accounts: ownership PDA, owner token account,
+// receiver, plant counter, the owner's collection page and the owner's profile.
+pub(crate) fn close_stale_record(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    plant_name: &str,
+    card_type: CardRarityInstruction,
+    quiz_id: Option<u64>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [ownership_account, token_account, receiver, plant_counter_account, collection_page_account, profile_account, ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (ownership_pda, _) =
+        find_ownership_address(program_id, plant_name, owner, card_type, quiz_id);
+    if ownership_pda != *ownership_account.key || ownership_account.owner != program_id {
+        msg!("Ownership account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let record = OwnershipRecord::try_from_slice(&ownership_account.data.borrow())?;
+    if record.owner != *owner {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if token_account.owner != &spl_token::id() && token_account.owner != &spl_token_2022::id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let token = StateWithExtensions::<TokenAccount>::unpack(&token_account.data.borrow())?.base;
+    if token.owner != *owner || token.mint != record.nft_mint {
+        msg!("Token account does not belong to this owner and mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Records can only be dropped once the card has left the wallet, otherwise the
+    // owner could re-mint the same plant and rarity while keeping the old token.
+    if token.amount > 0 {
+        msg!("Card is still held; use BurnCard instead");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // As in BurnCard, only discovery cards have a collection bit to clear.
+    if quiz_id.is_none() {
+        let plant_id = load_plant_id(program_id, plant_counter_account, plant_name)?;
+        remove_card(
+            program_id,
+            collection_page_account,
+            owner,
+            plant_id,
+            &record.rarity,
+        )?;
+    }
+
+    profile::remove_card(program_id, profile_account, owner, &record.rarity)?;
+
+    msg!("Closing stale ownership record for plant {}", plant_name);
+    Processor::close_program_account(ownership_account, receiver)
+}
+
+pub fn close_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    card_type: CardRarityInstruction,
+    quiz_id: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let owner = next_account_info(accounts_iter)?;
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    close_stale_record(
+        program_id,
+        owner.key,
+        plant_name,
+        card_type,
+        quiz_id,
+        accounts_iter.as_slice(),
+    )
+}
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        error::BiodexError,
+        instruction::{
+            CardRarityInstruction, CollectionPage, PlantCounter, PlantRegistry, CARD_RARITY_COUNT,
+            COLLECTION_PAGE_PLANTS,
+        },
+        processor::Processor,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn find_plant_registry_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"plant_registry"], program_id)
+}
+
+pub fn collection_page_index(plant_id: u64) -> u32 {
+    (plant_id / COLLECTION_PAGE_PLANTS) as u32
+}
+
+pub fn find_collection_page_address(program_id: &Pubkey, user: &Pubkey, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"collection", user.as_ref(), &page.to_le_bytes()],
+        program_id,
+    )
+}
+
+// Called once per plant, when its counter is first created.
+pub(crate) fn assign_plant_id<'a>(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<u64, ProgramError> {
+    let (registry_pda, registry_bump) = find_plant_registry_address(program_id);
+    if registry_pda != *registry_account.key {
+        msg!("Plant registry account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut registry = if registry_account.data_is_empty() {
+        PlantRegistry::default()
+    } else {
+        PlantRegistry::try_from_slice(&registry_account.data.borrow())?
+    };
+    let plant_id = registry.plant_count;
+    registry.plant_count += 1;
+
+    let serialized_registry = borsh::to_vec(&registry)?;
+    if registry_account.data_is_empty() {
+        Processor::create_pda_account(
+            payer,
+            registry_account,
+            system_program,
+            program_id,
+            serialized_registry.len(),
+            &[b"plant_registry", &[registry_bump]],
+        )?;
+    }
+    registry_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_registry);
+
+    Ok(plant_id)
+}
+
+pub(crate) fn load_plant_id(
+    program_id: &Pubkey,
+    plant_counter_account: &AccountInfo,
+    plant_name: &str,
+) -> Result<u64, ProgramError> {
+    let (plant_counter_pda, _) =
+        Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], program_id);
+    if plant_counter_pda != *plant_counter_account.key || plant_counter_account.owner != program_id
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(PlantCounter::try_from_slice(&plant_counter_account.data.borrow())?.plant_id)
+}
+
+pub(crate) fn check_page_account(
+    program_id: &Pubkey,
+    page_account: &AccountInfo,
+    user: &Pubkey,
+    plant_id: u64,
+) -> Result<u8, ProgramError> {
+    let (page_pda, page_bump) =
+        find_collection_page_address(program_id, user, collection_page_index(plant_id));
+    if page_pda != *page_account.key {
+        msg!("Collection page account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(page_bump)
+}
+
+pub(crate) fn holds_card(
+    program_id: &Pubkey,
+    page_account: &AccountInfo,
+    user: &Pubkey,
+    plant_id: u64,
+    rarity: &CardRarityInstruction,
+) -> Result<bool, ProgramError> {
+    check_page_account(program_id, page_account, user, plant_id)?;
+    if page_account.data_is_empty() {
+        return Ok(false);
+    }
+
+    Ok(CollectionPage::try_from_slice(&page_account.data.borrow())?.contains(rarity, plant_id))
+}
+
+// Sets the (rarity, plant) bit, creating the page on first use. Fails if it is already set.
+pub(crate) fn add_card<'a>(
+    program_id: &Pubkey,
+    page_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    user: &Pubkey,
+    plant_id: u64,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    let page_bump = check_page_account(program_id, page_account, user, plant_id)?;
+    let page_index = collection_page_index(plant_id);
+
+    let mut page = if page_account.data_is_empty() {
+        CollectionPage {
+            user: *user,
+            page: page_index,
+            bits: [[0; COLLECTION_PAGE_PLANTS as usize / 8]; CARD_RARITY_COUNT],
+        }
+    } else {
+        CollectionPage::try_from_slice(&page_account.data.borrow())?
+    };
+
+    if page.contains(rarity, plant_id) {
+        msg!(
+            "Collection already holds {:?} for plant {}",
+            rarity,
+            plant_id
+        );
+        return Err(BiodexError::CardAlreadyOwned.into());
+    }
+    page.insert(rarity, plant_id);
+
+    let serialized_page = borsh::to_vec(&page)?;
+    if page_account.data_is_empty() {
+        Processor::create_pda_account(
+            payer,
+            page_account,
+            system_program,
+            program_id,
+            serialized_page.len(),
+            &[
+                b"collection",
+                user.as_ref(),
+                &page_index.to_le_bytes(),
+                &[page_bump],
+            ],
+        )?;
+    }
+    page_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_page);
+
+    Ok(())
+}
+
+pub(crate) fn remove_card(
+    program_id: &Pubkey,
+    page_account: &AccountInfo,
+    user: &Pubkey,
+    plant_id: u64,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    check_page_account(program_id, page_account, user, plant_id)?;
+    if page_account.owner != program_id {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let mut page = CollectionPage::try_from_slice(&page_account.data.borrow())?;
+    page.remove(rarity, plant_id);
+
+    let serialized_page = borsh::to_vec(&page)?;
+    page_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_page);
+
+    Ok(())
+}
@@ -0,0 +1,410 @@
+use {
+    crate::{
+        burn::close_stale_record,
+        error::BiodexError,
+        instruction::{
+            CardRarityInstruction, ConfigUpdate, FusionRecipe, MintRateLimit, PauseScope,
+            ProgramConfig, Proposal, ReclaimTarget, Role,
+        },
+        processor::Processor,
+        proposal::{find_proposal_address, MAX_APPROVERS},
+        quiz::load_quiz,
+        rate_limit::{find_rate_limit_address, RATE_LIMIT_WINDOW},
+        roles::{initialize_role_registry, require_role},
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub const MAX_BPS: u32 = 10_000;
+
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], program_id)
+}
+
+pub(crate) fn load_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    let (config_pda, _) = find_config_address(program_id);
+    if config_pda != *config_account.key {
+        msg!("Config account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if config_account.owner != program_id {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    Ok(ProgramConfig::try_from_slice(
+        &config_account.data.borrow(),
+    )?)
+}
+
+// Mint paths run before the config exists, so an empty config account means defaults.
+pub(crate) fn load_config_or_default(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    if config_account.data_is_empty() {
+        let (config_pda, _) = find_config_address(program_id);
+        if config_pda != *config_account.key {
+            msg!("Config account does not match derived PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        return Ok(ProgramConfig::default());
+    }
+
+    load_config(program_id, config_account)
+}
+
+pub(crate) fn require_admin(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    admin: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    let config = load_config(program_id, config_account)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Signer is not the program admin");
+        return Err(BiodexError::Unauthorized.into());
+    }
+
+    Ok(config)
+}
+
+pub(crate) fn require_not_paused(config: &ProgramConfig, scope: PauseScope) -> ProgramResult {
+    if config.is_paused(scope) {
+        msg!("{:?} is paused", scope);
+        return Err(BiodexError::Paused.into());
+    }
+
+    Ok(())
+}
+
+pub fn initialize_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_data_account = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only whoever deployed the program, as its upgrade authority, may claim the admin key.
+    let (program_data_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if program_data_address != *program_data_account.key
+        || *program_data_account.owner != bpf_loader_upgradeable::id()
+    {
+        msg!("Program data account does not match this program");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let upgrade_authority = match bincode::deserialize(&program_data_account.data.borrow()) {
+        Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        }) => upgrade_authority_address,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    if upgrade_authority != Some(*admin.key) {
+        msg!("Signer is not the program upgrade authority");
+        return Err(BiodexError::Unauthorized.into());
+    }
+
+    let (config_pda, config_bump) = find_config_address(program_id);
+    if config_pda != *config_account.key {
+        msg!("Config account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if config_account.lamports() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let config = borsh::to_vec(&ProgramConfig {
+        admin: *admin.key,
+        ..ProgramConfig::default()
+    })?;
+
+    msg!("Initializing program config...");
+    Processor::create_pda_account(
+        payer,
+        config_account,
+        system_program,
+        program_id,
+        config.len(),
+        &[b"config", &[config_bump]],
+    )?;
+
+    config_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&config);
+
+    initialize_role_registry(
+        program_id,
+        role_registry_account,
+        admin.key,
+        payer,
+        system_program,
+    )
+}
+
+fn check_reclaim_pda(
+    program_id: &Pubkey,
+    target_account: &AccountInfo,
+    expected_pda: &Pubkey,
+) -> ProgramResult {
+    if *expected_pda != *target_account.key || target_account.owner != program_id {
+        msg!("Account to close does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+pub fn reclaim_rent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target: ReclaimTarget,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let fee_manager = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let target_account = next_account_info(accounts_iter)?;
+    let receiver = next_account_info(accounts_iter)?;
+
+    require_role(
+        program_id,
+        role_registry_account,
+        fee_manager,
+        Role::FeeManager,
+    )?;
+
+    let is_obsolete = match target {
+        ReclaimTarget::OwnershipRecord {
+            owner,
+            plant_name,
+            card_type,
+            quiz_id,
+        } => {
+            let token_account = next_account_info(accounts_iter)?;
+            let plant_counter_account = next_account_info(accounts_iter)?;
+            let collection_page_account = next_account_info(accounts_iter)?;
+            let profile_account = next_account_info(accounts_iter)?;
+
+            msg!("Reclaiming rent from {}", target_account.key);
+            return close_stale_record(
+                program_id,
+                &owner,
+                &plant_name,
+                card_type,
+                quiz_id,
+                &[
+                    target_account.clone(),
+                    token_account.clone(),
+                    receiver.clone(),
+                    plant_counter_account.clone(),
+                    collection_page_account.clone(),
+                    profile_account.clone(),
+                ],
+            );
+        }
+        ReclaimTarget::Quiz { quiz_id } => load_quiz(program_id, target_account, quiz_id)?
+            .is_obsolete(Clock::get()?.unix_timestamp),
+        ReclaimTarget::Proposal { proposal_id } => {
+            let (proposal_pda, _) = find_proposal_address(program_id, proposal_id);
+            check_reclaim_pda(program_id, target_account, &proposal_pda)?;
+            Proposal::try_from_slice(&target_account.data.borrow())?.executed
+        }
+        ReclaimTarget::RateLimit { user } => {
+            let (rate_limit_pda, _) = find_rate_limit_address(program_id, &user);
+            check_reclaim_pda(program_id, target_account, &rate_limit_pda)?;
+            let now = Clock::get()?.unix_timestamp;
+            MintRateLimit::try_from_slice(&target_account.data.borrow())?
+                .recent_mints
+                .iter()
+                .all(|minted_at| now - minted_at >= RATE_LIMIT_WINDOW)
+        }
+    };
+
+    if !is_obsolete {
+        msg!("{} is still in use", target_account.key);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    msg!("Reclaiming rent from {}", target_account.key);
+    Processor::close_program_account(target_account, receiver)
+}
+
+// Only capped discovery rarities take part in fusion; anything else ranks as None.
+fn fusion_tier(rarity: &CardRarityInstruction) -> Option<u8> {
+    match rarity {
+        CardRarityInstruction::GenesisFragment => Some(0),
+        CardRarityInstruction::AstralShard => Some(1),
+        CardRarityInstruction::MythicCrest => Some(2),
+        _ => None,
+    }
+}
+
+pub fn update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    update: ConfigUpdate,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    let mut config = require_admin(program_id, config_account, admin)?;
+
+    match update {
+        ConfigUpdate::SetSoulbound { rarity, enabled } => {
+            msg!("Setting soulbound for {:?} to {}", rarity, enabled);
+            config.soulbound.retain(|soulbound| *soulbound != rarity);
+            if enabled {
+                config.soulbound.push(rarity);
+            }
+        }
+        ConfigUpdate::SetMerkleLog { enabled } => {
+            msg!("Setting merkle ownership log to {}", enabled);
+            config.merkle_log = enabled;
+        }
+        ConfigUpdate::SetRarityOdds { odds } => {
+            if let Some(odds) = &odds {
+                if odds.mythic_bps as u32 + odds.astral_bps as u32 > MAX_BPS {
+                    msg!("Rarity odds add up to more than {} bps", MAX_BPS);
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+
+            msg!("Setting rarity odds to {:?}", odds);
+            config.rarity_odds = odds;
+        }
+        ConfigUpdate::SetActiveSeason { season_id } => {
+            msg!("Setting active season to {:?}", season_id);
+            config.active_season = season_id;
+        }
+        ConfigUpdate::SetSpeciesReview { enabled } => {
+            msg!("Setting species review to {}", enabled);
+            config.species_review = enabled;
+        }
+        ConfigUpdate::SetAdmin { admin } => {
+            msg!("Transferring admin to {}", admin);
+            config.admin = admin;
+        }
+        ConfigUpdate::SetMultisig { multisig } => {
+            if let Some(multisig) = &multisig {
+                let mut approvers = multisig.approvers.clone();
+                approvers.sort();
+                approvers.dedup();
+                if approvers.len() != multisig.approvers.len()
+                    || approvers.len() > MAX_APPROVERS
+                    || multisig.threshold == 0
+                    || multisig.threshold as usize > approvers.len()
+                    || multisig.timelock < 0
+                {
+                    msg!(
+                        "A multisig needs up to {} distinct approvers, a threshold between 1 and their count and a non-negative timelock",
+                        MAX_APPROVERS
+                    );
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+
+            msg!("Setting multisig to {:?}", multisig);
+            config.multisig = multisig;
+        }
+        ConfigUpdate::SetDailyMintLimit { limit } => {
+            msg!("Setting daily mint limit to {:?}", limit);
+            config.daily_mint_limit = limit;
+        }
+        ConfigUpdate::SetFusionRecipe {
+            input,
+            output,
+            input_count,
+        } => {
+            if input_count > 0 && fusion_tier(&output) <= fusion_tier(&input) {
+                msg!("Fusion must forge a higher discovery rarity");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            msg!(
+                "Setting fusion recipe: {} {:?} -> {:?}",
+                input_count,
+                input,
+                output
+            );
+            config.fusion_recipes.retain(|recipe| recipe.input != input);
+            if input_count > 0 {
+                config.fusion_recipes.push(FusionRecipe {
+                    input,
+                    output,
+                    input_count,
+                });
+            }
+        }
+    }
+
+    Processor::write_account_data(
+        config_account,
+        &borsh::to_vec(&config)?,
+        payer,
+        system_program,
+    )
+}
+
+pub fn set_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    scope: PauseScope,
+    paused: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let pauser = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    require_role(program_id, role_registry_account, pauser, Role::Pauser)?;
+
+    let mut config = load_config(program_id, config_account)?;
+    msg!("Setting {:?} paused to {}", scope, paused);
+    config.paused.retain(|paused_scope| *paused_scope != scope);
+    if paused {
+        config.paused.push(scope);
+    }
+
+    Processor::write_account_data(
+        config_account,
+        &borsh::to_vec(&config)?,
+        payer,
+        system_program,
+    )
+}
@@ -0,0 +1,29 @@
+use solana_program::program_error::ProgramError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BiodexError {
+    CardAlreadyOwned = 999,
+    QuizNotOpen,
+    QuizAlreadySubmitted,
+    InvalidQuizWindow,
+    QuizNotSettled,
+    QuizStillOpen,
+    QuizAlreadySettled,
+    CommitmentMismatch,
+    Unauthorized,
+    InvalidOwnershipProof,
+    OwnershipTreeFull,
+    SetIncomplete,
+    RandomnessNotReady,
+    SpeciesAlreadyRecorded,
+    ProposalNotReady,
+    Paused,
+    MintRateLimited,
+    QuizClaimsClosed,
+}
+
+impl From<BiodexError> for ProgramError {
+    fn from(e: BiodexError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
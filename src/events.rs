@@ -0,0 +1,119 @@
+use {
+    crate::instruction::CardRarityInstruction,
+    base64::{engine::general_purpose::STANDARD, Engine},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{log::sol_log_data, pubkey::Pubkey},
+};
+
+pub const EVENT_TAG: &[u8] = b"biodex";
+pub const EVENT_VERSION: u8 = 1;
+
+const PROGRAM_PREFIX: &str = "Program ";
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+// New variants must be appended so older indexers keep decoding the existing ones.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum BiodexEvent {
+    CardMinted {
+        owner: Pubkey,
+        plant_name: String,
+        rarity: CardRarityInstruction,
+        mint: Pubkey,
+        quiz_id: Option<u64>,
+    },
+    PlantFirstDiscovered {
+        plant_name: String,
+        discoverer: Pubkey,
+        new_species: bool,
+    },
+    QuizCardAwarded {
+        quiz_id: u64,
+        participant: Pubkey,
+        plant_name: String,
+        rarity: CardRarityInstruction,
+    },
+    CardBurned {
+        owner: Pubkey,
+        plant_name: String,
+        rarity: CardRarityInstruction,
+        mint: Pubkey,
+    },
+    CardTransferred {
+        from: Pubkey,
+        to: Pubkey,
+        plant_name: String,
+        rarity: CardRarityInstruction,
+        mint: Pubkey,
+    },
+    SetRewardClaimed {
+        set_id: u64,
+        user: Pubkey,
+        mint: Pubkey,
+    },
+    RegionFirstDiscovered {
+        plant_name: String,
+        region_id: u32,
+        discoverer: Pubkey,
+    },
+    OwnershipLeafAppended {
+        leaf_index: u64,
+        owner: Pubkey,
+        plant_name: String,
+        rarity: CardRarityInstruction,
+        timestamp: i64,
+    },
+}
+
+// Logged as three `Program data:` fields: tag, version, Borsh-encoded event.
+pub fn emit(event: &BiodexEvent) {
+    if let Ok(data) = borsh::to_vec(event) {
+        sol_log_data(&[EVENT_TAG, &[EVENT_VERSION], &data]);
+    }
+}
+
+fn decode_data(data: &str) -> Option<BiodexEvent> {
+    let fields = data
+        .split(' ')
+        .map(|field| STANDARD.decode(field).ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match fields.as_slice() {
+        [tag, version, data] if tag == EVENT_TAG && version == &[EVENT_VERSION] => {
+            BiodexEvent::try_from_slice(data).ok()
+        }
+        _ => None,
+    }
+}
+
+// Off-chain decoder for transaction log messages. `Program data:` lines are attributed to
+// the innermost program on the invoke stack, so only events logged by `program_id` itself
+// are returned; other programs cannot forge Biodex events by logging the same tag.
+pub fn decode_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<BiodexEvent> {
+    let program_id = program_id.to_string();
+    let mut invoke_stack = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invoke_stack.last() == Some(&program_id.as_str()) {
+                events.extend(decode_data(data));
+            }
+            continue;
+        }
+
+        let mut words = log
+            .strip_prefix(PROGRAM_PREFIX)
+            .unwrap_or_default()
+            .split(' ');
+        match (words.next(), words.next()) {
+            (Some(invoked), Some("invoke")) => invoke_stack.push(invoked),
+            (Some(_), Some("success" | "failed:")) => {
+                invoke_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
@@ -0,0 +1,285 @@
+use {
+    crate::{
+        collection::{add_card, load_plant_id, remove_card},
+        config::{load_config_or_default, require_not_paused},
+        events::{emit, BiodexEvent},
+        instruction::{
+            find_ownership_address, CardRarityInstruction, OwnershipRecord, PauseScope,
+            PlantCounter,
+        },
+        mint::check_token_program,
+        processor::Processor,
+        profile::{self, record_card},
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_token_2022::instruction as token_instruction,
+};
+
+// Accounts passed per consumed card: plant counter, ownership PDA, collection page.
+const ACCOUNTS_PER_INPUT: usize = 3;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct FuseArgs {
+    pub input: CardRarityInstruction,
+    // One consumed card per plant; a user holds at most one card of a rarity per plant.
+    pub input_plants: Vec<String>,
+    // Must be one of `input_plants`.
+    pub output_plant: String,
+}
+
+fn write_counter(plant_counter_account: &AccountInfo, counter: &PlantCounter) -> ProgramResult {
+    let serialized_counter = borsh::to_vec(counter)?;
+    plant_counter_account.try_borrow_mut_data()?[..serialized_counter.len()]
+        .copy_from_slice(&serialized_counter);
+    Ok(())
+}
+
+fn consume_card(
+    program_id: &Pubkey,
+    user: &AccountInfo,
+    plant_name: &str,
+    input: &CardRarityInstruction,
+    input_mint: &Pubkey,
+    input_accounts: &[AccountInfo],
+    profile_account: &AccountInfo,
+) -> ProgramResult {
+    let [plant_counter_account, ownership_account, collection_page_account] = input_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (ownership_pda, _) =
+        find_ownership_address(program_id, plant_name, user.key, input.clone(), None);
+    if ownership_pda != *ownership_account.key || ownership_account.owner != program_id {
+        msg!("Ownership account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let record = OwnershipRecord::try_from_slice(&ownership_account.data.borrow())?;
+    if record.owner != *user.key || record.nft_mint != *input_mint {
+        msg!("Ownership record does not belong to this holder and mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let plant_id = load_plant_id(program_id, plant_counter_account, plant_name)?;
+    remove_card(
+        program_id,
+        collection_page_account,
+        user.key,
+        plant_id,
+        input,
+    )?;
+
+    profile::remove_card(program_id, profile_account, user.key, input)?;
+
+    let mut counter = PlantCounter::try_from_slice(&plant_counter_account.data.borrow())?;
+    counter.burned_count += 1;
+    write_counter(plant_counter_account, &counter)?;
+
+    emit(&BiodexEvent::CardBurned {
+        owner: *user.key,
+        plant_name: plant_name.to_string(),
+        rarity: input.clone(),
+        mint: *input_mint,
+    });
+
+    Ok(())
+}
+
+pub fn fuse_cards<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: FuseArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let input_mint = next_account_info(accounts_iter)?;
+    let input_token_account = next_account_info(accounts_iter)?;
+    let output_mint = next_account_info(accounts_iter)?;
+    let mint_authority = next_account_info(accounts_iter)?;
+    let output_token_account = next_account_info(accounts_iter)?;
+    let output_ownership_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let rent = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let profile_account = next_account_info(accounts_iter)?;
+    let input_accounts = accounts_iter.as_slice();
+
+    if !user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    check_token_program(token_program)?;
+
+    let config = load_config_or_default(program_id, config_account)?;
+    require_not_paused(&config, PauseScope::Discovery)?;
+    let recipe = config.fusion_recipe(&args.input).ok_or_else(|| {
+        msg!("No fusion recipe for {:?}", args.input);
+        ProgramError::InvalidInstructionData
+    })?;
+
+    if args.input_plants.len() != recipe.input_count as usize {
+        msg!(
+            "Recipe needs {} {:?} cards, got {}",
+            recipe.input_count,
+            recipe.input,
+            args.input_plants.len()
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if input_accounts.len() < args.input_plants.len() * ACCOUNTS_PER_INPUT {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let output_index = args
+        .input_plants
+        .iter()
+        .position(|plant_name| *plant_name == args.output_plant)
+        .ok_or_else(|| {
+            msg!("Output plant must be one of the consumed plants");
+            ProgramError::InvalidInstructionData
+        })?;
+    if args
+        .input_plants
+        .iter()
+        .enumerate()
+        .any(|(index, plant_name)| args.input_plants[..index].contains(plant_name))
+    {
+        msg!("Each consumed card must come from a different plant");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (_, output_ownership_bump) = Processor::process_ownership_account(
+        output_ownership_account,
+        program_id,
+        &args.output_plant,
+        recipe.output.clone(),
+        None,
+        user,
+    )?;
+
+    msg!(
+        "Fusing {} {:?} cards into {:?}",
+        recipe.input_count,
+        recipe.input,
+        recipe.output
+    );
+    invoke(
+        &token_instruction::burn(
+            token_program.key,
+            input_token_account.key,
+            input_mint.key,
+            user.key,
+            &[],
+            recipe.input_count as u64,
+        )?,
+        &[
+            input_token_account.clone(),
+            input_mint.clone(),
+            user.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    for (plant_name, card_accounts) in args
+        .input_plants
+        .iter()
+        .zip(input_accounts.chunks(ACCOUNTS_PER_INPUT))
+    {
+        consume_card(
+            program_id,
+            user,
+            plant_name,
+            &recipe.input,
+            input_mint.key,
+            card_accounts,
+            profile_account,
+        )?;
+    }
+
+    let output_accounts = &input_accounts[output_index * ACCOUNTS_PER_INPUT..];
+    let (output_counter_account, output_page_account) = (&output_accounts[0], &output_accounts[2]);
+    let mut output_counter = PlantCounter::try_from_slice(&output_counter_account.data.borrow())?;
+    output_counter.fused_count += 1;
+    write_counter(output_counter_account, &output_counter)?;
+
+    add_card(
+        program_id,
+        output_page_account,
+        payer,
+        system_program,
+        user.key,
+        output_counter.plant_id,
+        &recipe.output,
+    )?;
+
+    Processor::ensure_associated_token_account(
+        output_token_account,
+        payer,
+        user,
+        output_mint,
+        system_program,
+        token_program,
+        rent,
+    )?;
+    Processor::nft_mint(
+        program_id,
+        token_program,
+        output_mint,
+        output_token_account,
+        mint_authority,
+        config.is_soulbound(&recipe.output),
+    )?;
+
+    Processor::create_ownership_record(
+        payer,
+        output_ownership_account,
+        system_program,
+        program_id,
+        &OwnershipRecord::new(
+            *user.key,
+            args.output_plant.clone(),
+            recipe.output.clone(),
+            *output_mint.key,
+            None,
+        ),
+        output_ownership_bump,
+    )?;
+
+    record_card(
+        program_id,
+        profile_account,
+        payer,
+        system_program,
+        user.key,
+        &args.output_plant,
+        &recipe.output,
+    )?;
+
+    emit(&BiodexEvent::CardMinted {
+        owner: *user.key,
+        plant_name: args.output_plant,
+        rarity: recipe.output.clone(),
+        mint: *output_mint.key,
+        quiz_id: None,
+    });
+
+    // Closed last: crediting the user before the token CPIs above would unbalance them.
+    for card_accounts in input_accounts
+        .chunks(ACCOUNTS_PER_INPUT)
+        .take(args.input_plants.len())
+    {
+        Processor::close_program_account(&card_accounts[1], user)?;
+    }
+
+    Ok(())
+}
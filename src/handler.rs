@@ -1,12 +1,13 @@
 use borsh::BorshDeserialize;
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::instruction::{CardRarityInstruction, OwnershipRecord};
+use crate::instruction::{find_ownership_address, CardRarityInstruction, OwnershipRecord};
 
 pub fn get_user_cards_for_plant(
     program_id: &Pubkey,
     user_wallet: &Pubkey,
     plant_name: &str,
+    quiz_ids: &[u64],
     accounts: &[AccountInfo],
 ) -> Result<Vec<OwnershipRecord>, ProgramError> {
     let mut owned_cards = Vec::new();
@@ -15,22 +16,35 @@ pub fn get_user_cards_for_plant(
         CardRarityInstruction::GenesisFragment,
         CardRarityInstruction::AstralShard,
         CardRarityInstruction::MythicCrest,
-        CardRarityInstruction::AscendantSeal,
         CardRarityInstruction::PrimordialRelic,
         CardRarityInstruction::AuroraSeed,
+        CardRarityInstruction::SeasonalBloom,
+        CardRarityInstruction::RegionalRelic,
+    ];
+
+    let quiz_card_types = [
+        CardRarityInstruction::AscendantSeal,
         CardRarityInstruction::CodexOfInsight,
     ];
 
-    for card_type in card_types.iter() {
-        let (ownership_pda, _) = Pubkey::find_program_address(
-            &[
-                plant_name.as_bytes(),
-                user_wallet.as_ref(),
-                &[card_type.clone() as u8],
-            ],
-            program_id,
-        );
+    let discovery_pdas = card_types.iter().map(|card_type| {
+        find_ownership_address(program_id, plant_name, user_wallet, card_type.clone(), None).0
+    });
+
+    let quiz_pdas = quiz_ids.iter().flat_map(|quiz_id| {
+        quiz_card_types.iter().map(move |card_type| {
+            find_ownership_address(
+                program_id,
+                plant_name,
+                user_wallet,
+                card_type.clone(),
+                Some(*quiz_id),
+            )
+            .0
+        })
+    });
 
+    for ownership_pda in discovery_pdas.chain(quiz_pdas) {
         if let Some(account) = accounts.iter().find(|a| a.key == &ownership_pda) {
             if !account.data_is_empty() {
                 if let Ok(record) = OwnershipRecord::try_from_slice(&account.data.borrow()) {
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
-
+use crate::fuse::FuseArgs;
+use crate::merkle::{VerifyOwnershipArgs, OWNERSHIP_TREE_DEPTH, OWNERSHIP_TREE_ROOT_HISTORY};
 use crate::mint::CreateTokenArgs;
+use crate::plant_set::CreatePlantSetArgs;
+use crate::quiz::CreateQuizArgs;
+use crate::season::CreateSeasonArgs;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
@@ -12,40 +15,263 @@ pub struct OwnershipRecord {
 
     pub rarity: CardRarityInstruction,
 
-
     pub nft_mint: Pubkey,
+
+    pub quiz_id: Option<u64>,
+
+    pub context: Option<DiscoveryContext>,
 }
 
 impl OwnershipRecord {
-    pub const MAX_SIZE: usize = 32 + 4 + 50 + 1 + 8 + 32;
+    pub const MAX_SIZE: usize = 32 + 4 + 50 + 1 + 8 + 32 + 9 + 1 + DiscoveryContext::MAX_SIZE;
 
     pub fn new(
         owner: Pubkey,
         plant_name: String,
         rarity: CardRarityInstruction,
         nft_mint: Pubkey,
+        quiz_id: Option<u64>,
     ) -> OwnershipRecord {
         Self {
             owner,
             plant_name,
             rarity,
             nft_mint,
+            quiz_id,
+            context: None,
+        }
+    }
+}
+
+pub const MAX_GEOHASH_LEN: usize = 5;
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+// Where and from what a card was discovered, as attested by the mint authority. The location is
+// kept coarse on purpose: a 5 character geohash is a cell of roughly 5 km.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiscoveryContext {
+    pub geohash: Option<String>,
+    pub region_id: Option<u32>,
+    // Content hash of the submitted photo.
+    pub image_hash: Option<[u8; 32]>,
+}
+
+impl DiscoveryContext {
+    pub const MAX_SIZE: usize = (1 + 4 + MAX_GEOHASH_LEN) + (1 + 4) + (1 + 32);
+
+    pub fn is_valid(&self) -> bool {
+        match &self.geohash {
+            Some(geohash) => {
+                geohash.len() <= MAX_GEOHASH_LEN
+                    && geohash.bytes().all(|c| GEOHASH_ALPHABET.contains(&c))
+            }
+            None => true,
         }
     }
 }
 
+// Quiz cards are keyed by quiz as well, so the same plant can be quizzed more than once.
+pub fn ownership_seeds<'a>(
+    plant_name: &'a str,
+    user_wallet: &'a Pubkey,
+    card_type: &'a [u8; 1],
+    quiz_id: Option<&'a [u8; 8]>,
+) -> Vec<&'a [u8]> {
+    let mut seeds: Vec<&[u8]> = vec![plant_name.as_bytes(), user_wallet.as_ref(), card_type];
+    if let Some(quiz_id) = quiz_id {
+        seeds.push(quiz_id);
+    }
+    seeds
+}
+
+pub fn find_ownership_address(
+    program_id: &Pubkey,
+    plant_name: &str,
+    user_wallet: &Pubkey,
+    card_type: CardRarityInstruction,
+    quiz_id: Option<u64>,
+) -> (Pubkey, u8) {
+    let card_seed = [card_type as u8];
+    let quiz_seed = quiz_id.map(u64::to_le_bytes);
+    let seeds = ownership_seeds(plant_name, user_wallet, &card_seed, quiz_seed.as_ref());
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
 pub enum ProgramInstruction {
     CreateMint {
         args: CreateTokenArgs,
     },
 
+    // Accounts 0-20 are fixed. They are followed by the Season PDA and Season Bloom mint
+    // while the config names an active season, then by the Region Counter PDA and
+    // RegionalRelic mint when `context` carries a region_id.
     MintNFT {
         card_type: CardRarityInstruction,
         plant_name: String,
         is_new_species: Option<bool>,
-        quiz_winner: Option<bool>,
+        context: Option<DiscoveryContext>,
+    },
+
+    CreateQuiz {
+        args: CreateQuizArgs,
+    },
+
+    CommitQuizAnswers {
+        quiz_id: u64,
+        commitment: [u8; 32],
+    },
+
+    SettleQuiz {
+        quiz_id: u64,
+        answers: Vec<u8>,
+        salt: [u8; 32],
+    },
+
+    SubmitQuizResult {
+        quiz_id: u64,
+        answers: Vec<u8>,
+        salt: [u8; 32],
+    },
+
+    BurnCard {
+        plant_name: String,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+    },
+
+    InitializeConfig,
+
+    ReclaimRent {
+        target: ReclaimTarget,
+    },
+
+    CloseOwnership {
+        plant_name: String,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+    },
+
+    TransferCard {
+        plant_name: String,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+    },
+
+    UpdateConfig {
+        update: ConfigUpdate,
+    },
+    InitializeOwnershipTree,
+    VerifyOwnership {
+        args: VerifyOwnershipArgs,
+    },
+    CreatePlantSet {
+        args: CreatePlantSetArgs,
+    },
+    ClaimSetReward {
+        set_id: u64,
+    },
+    Fuse {
+        args: FuseArgs,
+    },
+    RequestMint {
+        plant_name: String,
+        commitment: [u8; 32],
+    },
+    SettleMint {
+        plant_name: String,
+        seed: [u8; 32],
+        context: Option<DiscoveryContext>,
+    },
+    CreateSeason {
+        args: CreateSeasonArgs,
+    },
+    SubmitSpeciesClaim {
+        plant_name: String,
+        context: Option<DiscoveryContext>,
+    },
+    ApproveClaim {
+        plant_name: String,
+        user: Pubkey,
     },
+    // With `mint_regular` the claimant still gets a card, minted as a known species.
+    RejectClaim {
+        plant_name: String,
+        user: Pubkey,
+        mint_regular: bool,
+    },
+    GrantRole {
+        member: Pubkey,
+        role: Role,
+    },
+    RevokeRole {
+        member: Pubkey,
+        role: Role,
+    },
+    // `instruction` is a serialized ProgramInstruction, run by ExecuteProposal with the
+    // multisig authority PDA as signer over exactly `accounts`.
+    CreateProposal {
+        proposal_id: u64,
+        instruction: Vec<u8>,
+        accounts: Vec<ProposalAccount>,
+    },
+    ApproveProposal {
+        proposal_id: u64,
+    },
+    ExecuteProposal {
+        proposal_id: u64,
+    },
+    Pause {
+        scope: PauseScope,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseScope {
+    // MintNFT, SettleMint, species reviews, Fuse and ClaimSetReward
+    Discovery,
+    // SubmitQuizResult
+    Quizzes,
+    // Both of the above, plus CreateMint
+    All,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    // CreateMint, CreatePlantSet, CreateSeason
+    Curator,
+    // CreateQuiz
+    QuizMaster,
+    // Co-signs MintNFT, SettleMint and species reviews as mint authority
+    AttestationOracle,
+    // ReclaimRent
+    FeeManager,
+    // Pause, Unpause
+    Pauser,
+    // ApproveClaim, RejectClaim
+    Reviewer,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoleGrant {
+    pub member: Pubkey,
+    pub role: Role,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default)]
+pub struct RoleRegistry {
+    pub grants: Vec<RoleGrant>,
+}
+
+impl RoleRegistry {
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        self.grants
+            .iter()
+            .any(|grant| grant.member == *member && grant.role == role)
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
@@ -54,9 +280,10 @@ pub struct PlantInfo {
     pub rarity: CardRarityInstruction,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+// Hands out sequential plant ids, which index the per-user collection bitmaps.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default)]
 pub struct PlantRegistry {
-    pub plants: Vec<PlantInfo>,
+    pub plant_count: u64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
@@ -69,10 +296,378 @@ pub struct PlantCounter {
     pub common_count: u64,
     pub mastery_count: u64,
     pub codex_count: u64,
+    pub burned_count: u64,
+    pub first_minter: Option<Pubkey>,
+    pub plant_id: u64,
+    pub fused_count: u64,
+    pub discovery: DiscoveryState,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscoveryKind {
+    KnownSpecies,
+    NewSpecies,
+}
+
+// Who first found a plant, and whether it was a new species at the time.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DiscoveryState {
+    #[default]
+    Undiscovered,
+    Discovered {
+        kind: DiscoveryKind,
+        by: Pubkey,
+        at: i64,
+    },
+}
+
+impl DiscoveryState {
+    // Applies one attested discovery and returns the first-discovery card it earns, if any.
+    //
+    // Undiscovered -> Discovered(kind): PrimordialRelic or AuroraSeed.
+    // Discovered(KnownSpecies) + new species: reclassified to NewSpecies, AuroraSeed.
+    // Discovered(NewSpecies), or a known species again: unchanged, regular card.
+    pub fn discover(&mut self, is_new_species: bool, by: Pubkey, at: i64) -> Option<DiscoveryKind> {
+        let kind = if is_new_species {
+            DiscoveryKind::NewSpecies
+        } else {
+            DiscoveryKind::KnownSpecies
+        };
+
+        let awarded = match self {
+            DiscoveryState::Undiscovered => Some(kind),
+            DiscoveryState::Discovered {
+                kind: DiscoveryKind::KnownSpecies,
+                ..
+            } if kind == DiscoveryKind::NewSpecies => Some(kind),
+            DiscoveryState::Discovered { .. } => None,
+        };
+
+        if let Some(kind) = awarded {
+            *self = DiscoveryState::Discovered { kind, by, at };
+        }
+        awarded
+    }
+}
+
+// Per-region counterpart of PlantCounter, created by the first attested mint in the region.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct RegionCounter {
+    pub plant_name: String,
+    pub region_id: u32,
+    // Holder of the RegionalRelic, or the global first discoverer if they found it here.
     pub first_minter: Option<Pubkey>,
+    pub card_count: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub soulbound: Vec<CardRarityInstruction>,
+    pub merkle_log: bool,
+    pub fusion_recipes: Vec<FusionRecipe>,
+    // When set, regular cards of known plants are drawn through RequestMint and SettleMint.
+    pub rarity_odds: Option<RarityOdds>,
+    // Season consulted by the mint paths; it only applies between its start and end.
+    pub active_season: Option<u64>,
+    // When set, new species go through SubmitSpeciesClaim and a reviewer instead of MintNFT.
+    pub species_review: bool,
+    // Approvers of proposals run by the multisig authority PDA.
+    pub multisig: Option<Multisig>,
+    pub paused: Vec<PauseScope>,
+    // Most discovery and quiz cards a wallet can mint in any 24 hour window.
+    pub daily_mint_limit: Option<u16>,
+}
+
+impl ProgramConfig {
+    pub fn is_soulbound(&self, rarity: &CardRarityInstruction) -> bool {
+        self.soulbound.contains(rarity)
+    }
+
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused.contains(&scope) || self.paused.contains(&PauseScope::All)
+    }
+
+    pub fn fusion_recipe(&self, input: &CardRarityInstruction) -> Option<&FusionRecipe> {
+        self.fusion_recipes
+            .iter()
+            .find(|recipe| recipe.input == *input)
+    }
+}
+
+// `threshold` of the approvers must approve a proposal, and it can run `timelock` seconds
+// after the last approval it needed.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct Multisig {
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: i64,
+}
+
+impl Multisig {
+    pub fn is_approver(&self, key: &Pubkey) -> bool {
+        self.approvers.contains(key)
+    }
+
+    // Approvals from keys that were removed from the multisig no longer count.
+    pub fn approval_count(&self, approvals: &[Pubkey]) -> usize {
+        approvals
+            .iter()
+            .filter(|approver| self.is_approver(approver))
+            .count()
+    }
 }
 
+// Odds in basis points of drawing each capped tier; the remainder draws GenesisFragment.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct RarityOdds {
+    pub mythic_bps: u16,
+    pub astral_bps: u16,
+}
+
+// Burn `input_count` cards of `input` to forge one card of `output`. One recipe per input rarity.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct FusionRecipe {
+    pub input: CardRarityInstruction,
+    pub output: CardRarityInstruction,
+    pub input_count: u8,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub enum ConfigUpdate {
+    SetSoulbound {
+        rarity: CardRarityInstruction,
+        enabled: bool,
+    },
+    SetMerkleLog {
+        enabled: bool,
+    },
+    // An `input_count` of 0 removes the recipe for `input`.
+    SetFusionRecipe {
+        input: CardRarityInstruction,
+        output: CardRarityInstruction,
+        input_count: u8,
+    },
+    SetRarityOdds {
+        odds: Option<RarityOdds>,
+    },
+    SetActiveSeason {
+        season_id: Option<u64>,
+    },
+    SetSpeciesReview {
+        enabled: bool,
+    },
+    SetAdmin {
+        admin: Pubkey,
+    },
+    SetMultisig {
+        multisig: Option<Multisig>,
+    },
+    SetDailyMintLimit {
+        limit: Option<u16>,
+    },
+}
+
+// Obsolete accounts ReclaimRent can close. Anything else, or one of these still in use,
+// is refused.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub enum ReclaimTarget {
+    // A record whose card already left the owner's token account.
+    OwnershipRecord {
+        owner: Pubkey,
+        plant_name: String,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+    },
+    // A quiz whose answers were revealed.
+    Quiz {
+        quiz_id: u64,
+    },
+    // A proposal that already ran.
+    Proposal {
+        proposal_id: u64,
+    },
+    // A rate limit without a mint in the current window.
+    RateLimit {
+        user: Pubkey,
+    },
+}
+
+// Append-only Merkle tree of ownership leaves. Only the right-most path is kept,
+// plus a ring of recent roots so proofs built against a slightly stale root still verify.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct OwnershipTree {
+    pub leaf_count: u64,
+    pub filled_subtrees: [[u8; 32]; OWNERSHIP_TREE_DEPTH],
+    pub roots: [[u8; 32]; OWNERSHIP_TREE_ROOT_HISTORY],
+    pub root_index: u8,
+}
+
+// A group of registry plants, e.g. "Medicinal herbs". Holding a card of every plant
+// lets a user claim one card of `reward_mint`.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct PlantSet {
+    pub set_id: u64,
+    pub name: String,
+    pub plant_ids: Vec<u64>,
+    pub reward_mint: Pubkey,
+    pub claimed_count: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct SetClaim {
+    pub set_id: u64,
+    pub user: Pubkey,
+    pub claimed_at: i64,
+}
+
+// A seasonal campaign for the listed plants: raised epic and rare caps, boosted odds in
+// random mode, and a limited supply of SeasonalBloom cards handed out instead of GenesisFragment.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct Season {
+    pub season_id: u64,
+    pub name: String,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub plants: Vec<String>,
+    pub epic_cap_bonus: u64,
+    pub rare_cap_bonus: u64,
+    pub odds: Option<RarityOdds>,
+    // Boosted odds fade linearly back to the base odds by `ends_at`.
+    pub odds_decay: bool,
+    pub bloom_mint: Pubkey,
+    pub bloom_supply: u64,
+    pub blooms_minted: u64,
+}
+
+impl Season {
+    pub fn is_running(&self, now: i64) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn includes(&self, plant_name: &str) -> bool {
+        self.plants.iter().any(|plant| plant == plant_name)
+    }
+
+    pub fn blooms_left(&self) -> bool {
+        self.blooms_minted < self.bloom_supply
+    }
+
+    pub fn odds_at(&self, now: i64, base: Option<&RarityOdds>) -> Option<RarityOdds> {
+        let boosted = self.odds.clone()?;
+        if !self.odds_decay {
+            return Some(boosted);
+        }
+
+        let base = base.cloned().unwrap_or(RarityOdds {
+            mythic_bps: 0,
+            astral_bps: 0,
+        });
+        // Widened so timestamps anywhere in the i64 range cannot overflow.
+        let span = i128::from(self.ends_at) - i128::from(self.starts_at);
+        if span <= 0 {
+            return Some(base);
+        }
+        let elapsed = (i128::from(now) - i128::from(self.starts_at)).clamp(0, span);
+        let fade = |from: u16, to: u16| {
+            let (from, to) = (i128::from(from), i128::from(to));
+            (from + (to - from) * elapsed / span).clamp(from.min(to), from.max(to)) as u16
+        };
+
+        Some(RarityOdds {
+            mythic_bps: fade(boosted.mythic_bps, base.mythic_bps),
+            astral_bps: fade(boosted.astral_bps, base.astral_bps),
+        })
+    }
+}
+
+// A new-species claim waiting for a reviewer. The claim account's rent goes back to the user.
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct SpeciesClaim {
+    pub user: Pubkey,
+    pub plant_name: String,
+    pub context: Option<DiscoveryContext>,
+    pub submitted_at: i64,
+}
+
+// A privileged instruction waiting for multisig approval. `ready_at` is set once the
+// threshold is met and already includes the timelock.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub instruction: Vec<u8>,
+    pub accounts: Vec<ProposalAccount>,
+    pub approvals: Vec<Pubkey>,
+    pub ready_at: Option<i64>,
+    pub executed: bool,
+}
+
+// An AccountMeta of the proposed instruction, fixed when the proposal is created.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+// Timestamps of the user's mints in the current rate limit window.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct MintRateLimit {
+    pub user: Pubkey,
+    pub recent_mints: Vec<i64>,
+}
+
+// A pending random mint: the commitment hides the user's seed until a slot after `commit_slot`.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct MintRequest {
+    pub user: Pubkey,
+    pub plant_name: String,
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct Quiz {
+    pub quiz_id: u64,
+    pub authority: Pubkey,
+    pub plant_name: String,
+    pub answer_hash: [u8; 32],
+    pub opens_at: i64,
+    pub closes_at: i64,
+    pub max_winners: u64,
+    pub winner_count: u64,
+    pub participant_count: u64,
+    pub answers: Option<Vec<u8>>,
+    pub claimed_count: u64,
+    // Set by SettleQuiz. Results can be claimed until then; afterwards the quiz can be reclaimed.
+    pub claims_close_at: i64,
+}
+
+impl Quiz {
+    // Nothing is left to claim once every committed participant has claimed or the window closed.
+    pub fn is_obsolete(&self, now: i64) -> bool {
+        self.answers.is_some()
+            && (self.claimed_count >= self.participant_count || now > self.claims_close_at)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub enum QuizOutcome {
+    Pending,
+    Winner,
+    Participant,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct QuizResult {
+    pub quiz_id: u64,
+    pub participant: Pubkey,
+    pub commitment: [u8; 32],
+    pub outcome: QuizOutcome,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
 pub enum CardRarityInstruction {
     // Common
     GenesisFragment,
@@ -94,6 +689,12 @@ pub enum CardRarityInstruction {
 
     // Only first
     AuroraSeed,
+
+    // Seasonal
+    SeasonalBloom,
+
+    // First in a region
+    RegionalRelic,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
@@ -113,8 +714,62 @@ pub struct CardSupply {
     pub common_minted: u64,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
-pub struct UserCardOwnership {
+pub const CARD_RARITY_COUNT: usize = 9;
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct UserProfile {
     pub user: Pubkey,
-    pub owned_plants: HashMap<u64, CardRarityInstruction>,
+    // Cards held, indexed by `CardRarityInstruction as usize`.
+    pub cards_by_rarity: [u64; CARD_RARITY_COUNT],
+    pub first_discoveries: u64,
+    pub quiz_wins: u64,
+    // sha256 of each plant name the user has collected a card for.
+    pub plants: Vec<[u8; 32]>,
+}
+
+pub const COLLECTION_PAGE_PLANTS: u64 = 256;
+
+// One bit per (rarity, plant id) for the plants in this page. Pages are fixed-size,
+// so adding a card never reallocates.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct CollectionPage {
+    pub user: Pubkey,
+    pub page: u32,
+    pub bits: [[u8; COLLECTION_PAGE_PLANTS as usize / 8]; CARD_RARITY_COUNT],
+}
+
+impl CollectionPage {
+    fn bit(plant_id: u64) -> (usize, u8) {
+        let slot = (plant_id % COLLECTION_PAGE_PLANTS) as usize;
+        (slot / 8, 1 << (slot % 8))
+    }
+
+    pub fn contains(&self, rarity: &CardRarityInstruction, plant_id: u64) -> bool {
+        let (byte, mask) = Self::bit(plant_id);
+        self.bits[rarity.clone() as usize][byte] & mask != 0
+    }
+
+    pub fn insert(&mut self, rarity: &CardRarityInstruction, plant_id: u64) {
+        let (byte, mask) = Self::bit(plant_id);
+        self.bits[rarity.clone() as usize][byte] |= mask;
+    }
+
+    pub fn remove(&mut self, rarity: &CardRarityInstruction, plant_id: u64) {
+        let (byte, mask) = Self::bit(plant_id);
+        self.bits[rarity.clone() as usize][byte] &= !mask;
+    }
+}
+
+impl UserProfile {
+    pub fn total_cards(&self) -> u64 {
+        self.cards_by_rarity.iter().sum()
+    }
+
+    pub fn cards_of(&self, rarity: &CardRarityInstruction) -> u64 {
+        self.cards_by_rarity[rarity.clone() as usize]
+    }
+
+    pub fn distinct_plants(&self) -> u64 {
+        self.plants.len() as u64
+    }
 }
@@ -1,15 +1,46 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use burn::{burn_card, close_ownership};
+use config::{initialize_config, reclaim_rent, set_paused, update_config};
+use fuse::fuse_cards;
 use instruction::ProgramInstruction;
+use merkle::{initialize_ownership_tree, verify_ownership};
 use mint::create_token;
+use plant_set::{claim_set_reward, create_plant_set};
 use processor::Processor;
+use proposal::{approve_proposal, create_proposal, execute_proposal};
+use quiz::{commit_quiz_answers, create_quiz, settle_quiz};
+use randomness::{request_mint, settle_mint};
+use roles::{grant_role, revoke_role};
+use season::create_season;
 use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
+use species::{approve_claim, reject_claim, submit_species_claim};
+use transfer::transfer_card;
 
+pub mod burn;
+pub mod collection;
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod fuse;
+pub mod handler;
 pub mod instruction;
+pub mod merkle;
 pub mod mint;
+pub mod plant_set;
 pub mod processor;
+pub mod profile;
+pub mod proposal;
+pub mod quiz;
+pub mod randomness;
+pub mod rate_limit;
+pub mod region;
+pub mod roles;
+pub mod season;
+pub mod species;
+pub mod transfer;
 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
 pub struct CounterAccount {
@@ -31,20 +62,113 @@ pub fn process_instruction<'a, 'b, 'c, 'd>(
     let instruction = ProgramInstruction::try_from_slice(instruction_data)
         .map_err(|_| ProgramError::InvalidInstructionData)?;
     match instruction {
-        ProgramInstruction::CreateMint { args } => create_token(accounts, args)?,
+        ProgramInstruction::CreateMint { args } => create_token(program_id, accounts, args)?,
         ProgramInstruction::MintNFT {
             card_type,
             plant_name,
             is_new_species,
-            quiz_winner,
+            context,
         } => Processor::process(
             program_id,
             accounts,
             card_type,
             plant_name.as_str(),
             is_new_species,
-            quiz_winner,
+            context,
         )?,
+        ProgramInstruction::CreateQuiz { args } => create_quiz(program_id, accounts, args)?,
+        ProgramInstruction::CommitQuizAnswers {
+            quiz_id,
+            commitment,
+        } => commit_quiz_answers(program_id, accounts, quiz_id, commitment)?,
+        ProgramInstruction::SettleQuiz {
+            quiz_id,
+            answers,
+            salt,
+        } => settle_quiz(program_id, accounts, quiz_id, answers, salt)?,
+        ProgramInstruction::SubmitQuizResult {
+            quiz_id,
+            answers,
+            salt,
+        } => Processor::process_quiz_submission(program_id, accounts, quiz_id, &answers, &salt)?,
+        ProgramInstruction::BurnCard {
+            plant_name,
+            card_type,
+            quiz_id,
+        } => burn_card(program_id, accounts, &plant_name, card_type, quiz_id)?,
+        ProgramInstruction::InitializeConfig => initialize_config(program_id, accounts)?,
+        ProgramInstruction::ReclaimRent { target } => reclaim_rent(program_id, accounts, target)?,
+        ProgramInstruction::CloseOwnership {
+            plant_name,
+            card_type,
+            quiz_id,
+        } => close_ownership(program_id, accounts, &plant_name, card_type, quiz_id)?,
+        ProgramInstruction::TransferCard {
+            plant_name,
+            card_type,
+            quiz_id,
+        } => transfer_card(program_id, accounts, &plant_name, card_type, quiz_id)?,
+        ProgramInstruction::UpdateConfig { update } => update_config(program_id, accounts, update)?,
+        ProgramInstruction::InitializeOwnershipTree => {
+            initialize_ownership_tree(program_id, accounts)?
+        }
+        ProgramInstruction::VerifyOwnership { args } => {
+            verify_ownership(program_id, accounts, args)?
+        }
+        ProgramInstruction::CreatePlantSet { args } => {
+            create_plant_set(program_id, accounts, args)?
+        }
+        ProgramInstruction::ClaimSetReward { set_id } => {
+            claim_set_reward(program_id, accounts, set_id)?
+        }
+        ProgramInstruction::Fuse { args } => fuse_cards(program_id, accounts, args)?,
+        ProgramInstruction::RequestMint {
+            plant_name,
+            commitment,
+        } => request_mint(program_id, accounts, &plant_name, commitment)?,
+        ProgramInstruction::SettleMint {
+            plant_name,
+            seed,
+            context,
+        } => settle_mint(program_id, accounts, &plant_name, seed, context)?,
+        ProgramInstruction::CreateSeason { args } => create_season(program_id, accounts, args)?,
+        ProgramInstruction::SubmitSpeciesClaim {
+            plant_name,
+            context,
+        } => submit_species_claim(program_id, accounts, &plant_name, context)?,
+        ProgramInstruction::ApproveClaim { plant_name, user } => {
+            approve_claim(program_id, accounts, &plant_name, &user)?
+        }
+        ProgramInstruction::RejectClaim {
+            plant_name,
+            user,
+            mint_regular,
+        } => reject_claim(program_id, accounts, &plant_name, &user, mint_regular)?,
+        ProgramInstruction::GrantRole { member, role } => {
+            grant_role(program_id, accounts, member, role)?
+        }
+        ProgramInstruction::RevokeRole { member, role } => {
+            revoke_role(program_id, accounts, member, role)?
+        }
+        ProgramInstruction::CreateProposal {
+            proposal_id,
+            instruction,
+            accounts: instruction_accounts,
+        } => create_proposal(
+            program_id,
+            accounts,
+            proposal_id,
+            instruction,
+            instruction_accounts,
+        )?,
+        ProgramInstruction::ApproveProposal { proposal_id } => {
+            approve_proposal(program_id, accounts, proposal_id)?
+        }
+        ProgramInstruction::ExecuteProposal { proposal_id } => {
+            execute_proposal(program_id, accounts, proposal_id)?
+        }
+        ProgramInstruction::Pause { scope } => set_paused(program_id, accounts, scope, true)?,
+        ProgramInstruction::Unpause { scope } => set_paused(program_id, accounts, scope, false)?,
     };
     Ok(())
 }
@@ -0,0 +1,270 @@
+use {
+    crate::{
+        config::require_admin,
+        error::BiodexError,
+        events::{emit, BiodexEvent},
+        instruction::{CardRarityInstruction, OwnershipTree},
+        processor::Processor,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hashv,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub const OWNERSHIP_TREE_DEPTH: usize = 20;
+pub const OWNERSHIP_TREE_ROOT_HISTORY: usize = 16;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct VerifyOwnershipArgs {
+    pub owner: Pubkey,
+    pub plant_name: String,
+    pub rarity: CardRarityInstruction,
+    pub timestamp: i64,
+    pub leaf_index: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+pub fn find_ownership_tree_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ownership_tree"], program_id)
+}
+
+pub fn ownership_leaf(
+    owner: &Pubkey,
+    plant_name: &str,
+    rarity: &CardRarityInstruction,
+    timestamp: i64,
+) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        owner.as_ref(),
+        plant_name.as_bytes(),
+        &[rarity.clone() as u8],
+        &timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+// zero_hashes()[level] is the root of an empty subtree of that height.
+fn zero_hashes() -> [[u8; 32]; OWNERSHIP_TREE_DEPTH + 1] {
+    let mut zeros = [[0u8; 32]; OWNERSHIP_TREE_DEPTH + 1];
+    for level in 0..OWNERSHIP_TREE_DEPTH {
+        zeros[level + 1] = hash_nodes(&zeros[level], &zeros[level]);
+    }
+    zeros
+}
+
+pub fn compute_root(leaf: [u8; 32], leaf_index: u64, proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut node = leaf;
+    let mut index = leaf_index;
+    for sibling in proof {
+        node = if index & 1 == 0 {
+            hash_nodes(&node, sibling)
+        } else {
+            hash_nodes(sibling, &node)
+        };
+        index /= 2;
+    }
+    node
+}
+
+// Off-chain helper: rebuilds the tree from every leaf appended so far (in order) and
+// returns the sibling path for `leaf_index`.
+pub fn build_proof(leaves: &[[u8; 32]], leaf_index: usize) -> Vec<[u8; 32]> {
+    let zeros = zero_hashes();
+    let mut proof = Vec::with_capacity(OWNERSHIP_TREE_DEPTH);
+    let mut level_nodes = leaves.to_vec();
+    let mut index = leaf_index;
+
+    for zero in zeros.iter().take(OWNERSHIP_TREE_DEPTH) {
+        proof.push(*level_nodes.get(index ^ 1).unwrap_or(zero));
+
+        level_nodes = level_nodes
+            .chunks(2)
+            .map(|pair| hash_nodes(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+        index /= 2;
+    }
+
+    proof
+}
+
+pub fn empty_ownership_tree() -> OwnershipTree {
+    let mut roots = [[0u8; 32]; OWNERSHIP_TREE_ROOT_HISTORY];
+    roots[0] = zero_hashes()[OWNERSHIP_TREE_DEPTH];
+
+    OwnershipTree {
+        leaf_count: 0,
+        filled_subtrees: [[0u8; 32]; OWNERSHIP_TREE_DEPTH],
+        roots,
+        root_index: 0,
+    }
+}
+
+pub fn current_root(tree: &OwnershipTree) -> [u8; 32] {
+    tree.roots[tree.root_index as usize]
+}
+
+fn is_known_root(tree: &OwnershipTree, root: &[u8; 32]) -> bool {
+    *root != [0u8; 32] && tree.roots.contains(root)
+}
+
+fn append_leaf(tree: &mut OwnershipTree, leaf: [u8; 32]) -> Result<u64, ProgramError> {
+    let leaf_index = tree.leaf_count;
+    if leaf_index >= 1 << OWNERSHIP_TREE_DEPTH {
+        msg!("Ownership tree is full");
+        return Err(BiodexError::OwnershipTreeFull.into());
+    }
+
+    let zeros = zero_hashes();
+    let mut node = leaf;
+    let mut index = leaf_index;
+    for (filled_subtree, zero) in tree.filled_subtrees.iter_mut().zip(zeros.iter()) {
+        node = if index & 1 == 0 {
+            *filled_subtree = node;
+            hash_nodes(&node, zero)
+        } else {
+            hash_nodes(filled_subtree, &node)
+        };
+        index /= 2;
+    }
+
+    tree.root_index = ((tree.root_index as usize + 1) % OWNERSHIP_TREE_ROOT_HISTORY) as u8;
+    tree.roots[tree.root_index as usize] = node;
+    tree.leaf_count += 1;
+
+    Ok(leaf_index)
+}
+
+fn load_ownership_tree(
+    program_id: &Pubkey,
+    tree_account: &AccountInfo,
+) -> Result<OwnershipTree, ProgramError> {
+    let (tree_pda, _) = find_ownership_tree_address(program_id);
+    if tree_pda != *tree_account.key {
+        msg!("Ownership tree account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if tree_account.owner != program_id {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    Ok(OwnershipTree::try_from_slice(&tree_account.data.borrow())?)
+}
+
+pub(crate) fn append_ownership_leaf(
+    program_id: &Pubkey,
+    tree_account: &AccountInfo,
+    owner: &Pubkey,
+    plant_name: &str,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    let mut tree = load_ownership_tree(program_id, tree_account)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let leaf_index = append_leaf(
+        &mut tree,
+        ownership_leaf(owner, plant_name, rarity, timestamp),
+    )?;
+
+    let serialized_tree = borsh::to_vec(&tree)?;
+    tree_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_tree);
+
+    // Indexers rebuild proofs from these events, so they carry every hashed field.
+    emit(&BiodexEvent::OwnershipLeafAppended {
+        leaf_index,
+        owner: *owner,
+        plant_name: plant_name.to_string(),
+        rarity: rarity.clone(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn initialize_ownership_tree(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let tree_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    require_admin(program_id, config_account, admin)?;
+
+    let (tree_pda, tree_bump) = find_ownership_tree_address(program_id);
+    if tree_pda != *tree_account.key {
+        msg!("Ownership tree account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if tree_account.lamports() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let tree = borsh::to_vec(&empty_ownership_tree())?;
+
+    msg!("Initializing ownership tree...");
+    Processor::create_pda_account(
+        payer,
+        tree_account,
+        system_program,
+        program_id,
+        tree.len(),
+        &[b"ownership_tree", &[tree_bump]],
+    )?;
+
+    tree_account.try_borrow_mut_data()?.copy_from_slice(&tree);
+
+    Ok(())
+}
+
+pub fn verify_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: VerifyOwnershipArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let tree_account = next_account_info(accounts_iter)?;
+
+    let tree = load_ownership_tree(program_id, tree_account)?;
+
+    if args.proof.len() != OWNERSHIP_TREE_DEPTH || args.leaf_index >= tree.leaf_count {
+        return Err(BiodexError::InvalidOwnershipProof.into());
+    }
+
+    let leaf = ownership_leaf(&args.owner, &args.plant_name, &args.rarity, args.timestamp);
+    let root = compute_root(leaf, args.leaf_index, &args.proof);
+    if !is_known_root(&tree, &root) {
+        msg!("Proof does not match a recent ownership root");
+        return Err(BiodexError::InvalidOwnershipProof.into());
+    }
+
+    msg!(
+        "Verified {:?} {} for {}",
+        args.rarity,
+        args.plant_name,
+        args.owner
+    );
+
+    Ok(())
+}
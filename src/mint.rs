@@ -1,16 +1,25 @@
 use {
+    crate::{
+        config::{load_config_or_default, require_not_paused},
+        instruction::{PauseScope, Role},
+        roles::require_role,
+    },
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
-        program::invoke,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
         program_pack::Pack,
+        pubkey::Pubkey,
         rent::Rent,
         system_instruction,
         sysvar::Sysvar,
     },
     spl_token::{instruction as token_instruction, state::Mint},
+    spl_token_2022::extension::{metadata_pointer, ExtensionType},
+    spl_token_metadata_interface::state::TokenMetadata,
 };
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
@@ -18,17 +27,84 @@ pub struct CreateTokenArgs {
     pub nft_title: String,
     pub nft_symbol: String,
     pub nft_uri: String,
+    // Only honoured by Token-2022 mints; meant for soulbound rarities.
+    pub non_transferable: bool,
+}
+
+// Mint and freeze authority of every card mint. The program signs for it, so role holders
+// authorize mints with their own signature and never hold the SPL authority.
+pub fn find_mint_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"mint_authority"], program_id)
+}
+
+pub(crate) fn check_mint_authority(
+    program_id: &Pubkey,
+    mint_authority: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (mint_authority_pda, mint_authority_bump) = find_mint_authority_address(program_id);
+    if mint_authority_pda != *mint_authority.key {
+        msg!("Mint authority does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(mint_authority_bump)
+}
+
+pub(crate) fn check_token_program(token_program: &AccountInfo) -> ProgramResult {
+    if *token_program.key != spl_token::id() && *token_program.key != spl_token_2022::id() {
+        msg!("Unsupported token program {}", token_program.key);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
 }
 
-pub fn create_token(accounts: &[AccountInfo], _args: CreateTokenArgs) -> ProgramResult {
+pub fn create_token(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateTokenArgs,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let mint_account = next_account_info(accounts_iter)?;
-    let mint_authority = next_account_info(accounts_iter)?;
+    let curator = next_account_info(accounts_iter)?;
     let payer = next_account_info(accounts_iter)?;
     let rent = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     let token_program = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let mint_authority = next_account_info(accounts_iter)?;
+
+    require_not_paused(
+        &load_config_or_default(program_id, config_account)?,
+        PauseScope::All,
+    )?;
+    require_role(program_id, role_registry_account, curator, Role::Curator)?;
+    let mint_authority_bump = check_mint_authority(program_id, mint_authority)?;
+    check_token_program(token_program)?;
+    if *token_program.key == spl_token_2022::id() {
+        create_token_2022(
+            mint_account,
+            curator,
+            mint_authority,
+            payer,
+            system_program,
+            token_program,
+            &args,
+        )?;
+        return write_token_metadata(
+            mint_account,
+            curator,
+            mint_authority,
+            mint_authority_bump,
+            token_program,
+            args,
+        );
+    }
+
+    if args.non_transferable {
+        msg!("Non-transferable mints require Token-2022");
+        return Err(ProgramError::InvalidInstructionData);
+    }
 
     msg!("Creating mint account...");
     msg!("Mint: {}", mint_account.key);
@@ -58,12 +134,120 @@ pub fn create_token(accounts: &[AccountInfo], _args: CreateTokenArgs) -> Program
             Some(mint_authority.key),
             0,
         )?,
+        &[mint_account.clone(), token_program.clone(), rent.clone()],
+    )?;
+
+    Ok(())
+}
+
+fn create_token_2022<'a>(
+    mint_account: &AccountInfo<'a>,
+    curator: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    args: &CreateTokenArgs,
+) -> ProgramResult {
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if args.non_transferable {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    let mint_len =
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+
+    // The metadata TLV entry is appended by the token program after the mint
+    // is initialized, so fund for it up front and only allocate the mint.
+    let metadata_len = TokenMetadata {
+        name: args.nft_title.clone(),
+        symbol: args.nft_symbol.clone(),
+        uri: args.nft_uri.clone(),
+        ..Default::default()
+    }
+    .tlv_size_of()?;
+
+    msg!("Creating Token-2022 mint account...");
+    msg!("Mint: {}", mint_account.key);
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            mint_account.key,
+            (Rent::get()?).minimum_balance(mint_len + metadata_len),
+            mint_len as u64,
+            token_program.key,
+        ),
+        &[
+            mint_account.clone(),
+            payer.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program.key,
+            mint_account.key,
+            Some(*curator.key),
+            Some(*mint_account.key),
+        )?,
+        &[mint_account.clone(), token_program.clone()],
+    )?;
+
+    if args.non_transferable {
+        msg!("Marking mint as non-transferable");
+        invoke(
+            &spl_token_2022::instruction::initialize_non_transferable_mint(
+                token_program.key,
+                mint_account.key,
+            )?,
+            &[mint_account.clone(), token_program.clone()],
+        )?;
+    }
+
+    msg!("Initializing the mint account...");
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program.key,
+            mint_account.key,
+            mint_authority.key,
+            Some(mint_authority.key),
+            0,
+        )?,
+        &[mint_account.clone(), token_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+// The curator keeps the metadata update authority; only the mint authority is the PDA.
+fn write_token_metadata<'a>(
+    mint_account: &AccountInfo<'a>,
+    curator: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    mint_authority_bump: u8,
+    token_program: &AccountInfo<'a>,
+    args: CreateTokenArgs,
+) -> ProgramResult {
+    msg!("Writing token metadata...");
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program.key,
+            mint_account.key,
+            curator.key,
+            mint_account.key,
+            mint_authority.key,
+            args.nft_title,
+            args.nft_symbol,
+            args.nft_uri,
+        ),
         &[
             mint_account.clone(),
+            curator.clone(),
             mint_authority.clone(),
             token_program.clone(),
-            rent.clone(),
         ],
+        &[&[b"mint_authority", &[mint_authority_bump]]],
     )?;
 
     Ok(())
@@ -0,0 +1,261 @@
+use {
+    crate::{
+        config::{load_config_or_default, require_not_paused},
+        error::BiodexError,
+        events::{emit, BiodexEvent},
+        handler::get_user_cards_for_plant,
+        instruction::{PauseScope, PlantCounter, PlantSet, Role, SetClaim},
+        mint::check_token_program,
+        processor::Processor,
+        roles::require_role,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+// Each plant in a set costs two accounts in ClaimSetReward, so sets stay small.
+pub const MAX_SET_PLANTS: usize = 16;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct CreatePlantSetArgs {
+    pub set_id: u64,
+    pub name: String,
+    pub plant_ids: Vec<u64>,
+    pub reward_mint: Pubkey,
+}
+
+pub fn find_plant_set_address(program_id: &Pubkey, set_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"plant_set", &set_id.to_le_bytes()], program_id)
+}
+
+pub fn find_set_claim_address(program_id: &Pubkey, set_id: u64, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"set_claim", &set_id.to_le_bytes(), user.as_ref()],
+        program_id,
+    )
+}
+
+pub fn create_plant_set(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreatePlantSetArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let curator = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let set_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    require_role(program_id, role_registry_account, curator, Role::Curator)?;
+
+    if args.plant_ids.is_empty() || args.plant_ids.len() > MAX_SET_PLANTS {
+        msg!("A set holds between 1 and {} plants", MAX_SET_PLANTS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut sorted_ids = args.plant_ids.clone();
+    sorted_ids.sort_unstable();
+    sorted_ids.dedup();
+    if sorted_ids.len() != args.plant_ids.len() {
+        msg!("Set lists the same plant more than once");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (set_pda, set_bump) = find_plant_set_address(program_id, args.set_id);
+    if set_pda != *set_account.key {
+        msg!("Plant set account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if set_account.lamports() > 0 {
+        msg!("Plant set {} already exists", args.set_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let set = borsh::to_vec(&PlantSet {
+        set_id: args.set_id,
+        name: args.name,
+        plant_ids: args.plant_ids,
+        reward_mint: args.reward_mint,
+        claimed_count: 0,
+    })?;
+
+    msg!("Creating plant set {}...", args.set_id);
+    Processor::create_pda_account(
+        payer,
+        set_account,
+        system_program,
+        program_id,
+        set.len(),
+        &[b"plant_set", &args.set_id.to_le_bytes(), &[set_bump]],
+    )?;
+
+    set_account.try_borrow_mut_data()?.copy_from_slice(&set);
+
+    Ok(())
+}
+
+// Checks one (plant counter, ownership record) pair against the set's plant id.
+fn holds_plant(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    plant_id: u64,
+    plant_counter_account: &AccountInfo,
+    ownership_account: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    if plant_counter_account.owner != program_id || plant_counter_account.data_is_empty() {
+        return Ok(false);
+    }
+    let counter = PlantCounter::try_from_slice(&plant_counter_account.data.borrow())?;
+    let (plant_counter_pda, _) = Pubkey::find_program_address(
+        &[b"plant_counter", counter.plant_name.as_bytes()],
+        program_id,
+    );
+    if plant_counter_pda != *plant_counter_account.key || counter.plant_id != plant_id {
+        return Ok(false);
+    }
+
+    if ownership_account.owner != program_id {
+        return Ok(false);
+    }
+    let cards = get_user_cards_for_plant(
+        program_id,
+        user,
+        &counter.plant_name,
+        &[],
+        std::slice::from_ref(ownership_account),
+    )?;
+
+    Ok(cards.iter().any(|card| card.owner == *user))
+}
+
+pub fn claim_set_reward<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    set_id: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let set_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let reward_mint = next_account_info(accounts_iter)?;
+    let mint_authority = next_account_info(accounts_iter)?;
+    let associated_token_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let rent = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    check_token_program(token_program)?;
+    require_not_paused(
+        &load_config_or_default(program_id, config_account)?,
+        PauseScope::Discovery,
+    )?;
+
+    let (set_pda, _) = find_plant_set_address(program_id, set_id);
+    if set_pda != *set_account.key || set_account.owner != program_id {
+        msg!("Plant set account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut set = PlantSet::try_from_slice(&set_account.data.borrow())?;
+
+    if set.reward_mint != *reward_mint.key {
+        msg!("Reward mint does not match the plant set");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (claim_pda, claim_bump) = find_set_claim_address(program_id, set_id, user.key);
+    if claim_pda != *claim_account.key {
+        msg!("Set claim account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if claim_account.lamports() > 0 {
+        msg!("Set {} already claimed", set_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The remaining accounts are one (plant counter, ownership PDA) pair per set plant, in set order.
+    for &plant_id in &set.plant_ids {
+        let plant_counter_account = next_account_info(accounts_iter)?;
+        let ownership_account = next_account_info(accounts_iter)?;
+        if !holds_plant(
+            program_id,
+            user.key,
+            plant_id,
+            plant_counter_account,
+            ownership_account,
+        )? {
+            msg!("Missing a card for plant {} of set {}", plant_id, set_id);
+            return Err(BiodexError::SetIncomplete.into());
+        }
+    }
+
+    let claim = borsh::to_vec(&SetClaim {
+        set_id,
+        user: *user.key,
+        claimed_at: Clock::get()?.unix_timestamp,
+    })?;
+    Processor::create_pda_account(
+        payer,
+        claim_account,
+        system_program,
+        program_id,
+        claim.len(),
+        &[
+            b"set_claim",
+            &set_id.to_le_bytes(),
+            user.key.as_ref(),
+            &[claim_bump],
+        ],
+    )?;
+    claim_account.try_borrow_mut_data()?.copy_from_slice(&claim);
+
+    set.claimed_count += 1;
+    set_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&borsh::to_vec(&set)?);
+
+    Processor::ensure_associated_token_account(
+        associated_token_account,
+        payer,
+        user,
+        reward_mint,
+        system_program,
+        token_program,
+        rent,
+    )?;
+
+    msg!("Minting completion card for set {}", set.name);
+    Processor::nft_mint(
+        program_id,
+        token_program,
+        reward_mint,
+        associated_token_account,
+        mint_authority,
+        false,
+    )?;
+
+    emit(&BiodexEvent::SetRewardClaimed {
+        set_id,
+        user: *user.key,
+        mint: *reward_mint.key,
+    });
+
+    Ok(())
+}
@@ -1,22 +1,61 @@
-use crate::instruction::{CardRarityInstruction, OwnershipRecord, PlantCounter};
+use crate::collection::{add_card, assign_plant_id, holds_card};
+use crate::config::{load_config_or_default, require_not_paused};
+use crate::error::BiodexError;
+use crate::events::{emit, BiodexEvent};
+use crate::instruction::{
+    find_ownership_address, ownership_seeds, CardRarityInstruction, DiscoveryContext,
+    DiscoveryKind, DiscoveryState, OwnershipRecord, PauseScope, PlantCounter, QuizOutcome,
+    QuizResult, Role, MAX_GEOHASH_LEN,
+};
+use crate::merkle::append_ownership_leaf;
+use crate::mint::{check_mint_authority, check_token_program};
+use crate::profile::record_card;
+use crate::quiz::{find_quiz_result_address, load_quiz, participant_commitment};
+use crate::rate_limit::record_mint;
+use crate::region::{load_region, save_region};
+use crate::roles::require_role;
+use crate::season::{load_active_season, save_season};
 use borsh::BorshDeserialize;
+use constant_time_eq::constant_time_eq;
 use solana_program::program::invoke_signed;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction,
+    system_instruction, system_program,
     sysvar::{rent::Rent, Sysvar},
 };
 use spl_associated_token_account::instruction as associated_token_account_instruction;
-use spl_token::instruction as token_instruction;
+use spl_token_2022::{
+    extension::StateWithExtensions, instruction as token_instruction,
+    state::Account as TokenAccount,
+};
 
 const MAX_EPIC: u64 = 5;
 const MAX_RARE: u64 = 10;
 
+// MintNFT accounts; seasonal accounts (season PDA, bloom mint) follow them, then the regional
+// ones (region counter PDA, RegionalRelic mint) when the discovery context names a region.
+pub(crate) const MINT_ACCOUNT_COUNT: usize = 21;
+pub(crate) const MINT_CONFIG_INDEX: usize = 14;
+const SEASON_ACCOUNT_COUNT: usize = 2;
+
+// How regular (non-first) cards are handed out for one mint.
+struct RegularRules<'a> {
+    // The rolled tier in random mode; capped tiers fall through to the next one.
+    ceiling: CardRarityInstruction,
+    max_epic: u64,
+    max_rare: u64,
+    // Set while a running season still has blooms; they replace GenesisFragment.
+    bloom_mint: Option<&'a AccountInfo<'a>>,
+    // Set while the attested region has no first discoverer; it beats every regular tier.
+    regional_relic_mint: Option<&'a AccountInfo<'a>>,
+}
+
 pub struct Processor {}
 
 impl Processor {
@@ -26,54 +65,125 @@ impl Processor {
         instruction: CardRarityInstruction,
         plant_name: &str,
         is_new_species: Option<bool>,
-        quiz_winner: Option<bool>,
+        context: Option<DiscoveryContext>,
     ) -> ProgramResult {
+        if is_new_species == Some(true) {
+            let config_account = accounts
+                .get(MINT_CONFIG_INDEX)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if load_config_or_default(program_id, config_account)?.species_review {
+                msg!("New species claims need review; use SubmitSpeciesClaim");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
         match instruction {
             CardRarityInstruction::GenesisFragment
             | CardRarityInstruction::AstralShard
             | CardRarityInstruction::MythicCrest
             | CardRarityInstruction::PrimordialRelic
-            | CardRarityInstruction::AuroraSeed => Self::process_minting(
+            | CardRarityInstruction::AuroraSeed
+            | CardRarityInstruction::SeasonalBloom
+            | CardRarityInstruction::RegionalRelic => Self::process_minting(
                 program_id,
                 accounts,
-                instruction,
                 plant_name,
                 is_new_species,
+                None,
+                context,
+                accounts.get(MINT_ACCOUNT_COUNT..).unwrap_or_default(),
             ),
-            CardRarityInstruction::CodexOfInsight => Self::process_quiz(
-                program_id,
-                accounts,
-                instruction,
-                plant_name,
-                quiz_winner.unwrap(),
-            ),
+            CardRarityInstruction::CodexOfInsight | CardRarityInstruction::AscendantSeal => {
+                msg!("Quiz cards are awarded through SubmitQuizResult");
+                Err(ProgramError::InvalidInstructionData)
+            }
+        }?;
 
-            CardRarityInstruction::AscendantSeal => Self::process_quiz(
+        Ok(())
+    }
+
+    pub(crate) fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        program_id: &Pubkey,
+        space: usize,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                Rent::get()?.minimum_balance(space),
+                space as u64,
                 program_id,
-                accounts,
-                instruction,
-                plant_name,
-                quiz_winner.unwrap(),
             ),
-        }?;
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    }
+
+    pub(crate) fn write_account_data<'a>(
+        account: &AccountInfo<'a>,
+        data: &[u8],
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if data.len() != account.data_len() {
+            account.realloc(data.len(), false)?;
+
+            let required_lamports = Rent::get()?.minimum_balance(data.len());
+            if required_lamports > account.lamports() {
+                invoke(
+                    &system_instruction::transfer(
+                        payer.key,
+                        account.key,
+                        required_lamports - account.lamports(),
+                    ),
+                    &[payer.clone(), account.clone(), system_program.clone()],
+                )?;
+            }
+        }
+
+        account.try_borrow_mut_data()?.copy_from_slice(data);
 
         Ok(())
     }
 
-    fn process_ownership_account(
+    pub(crate) fn close_program_account(
+        account: &AccountInfo,
+        receiver: &AccountInfo,
+    ) -> ProgramResult {
+        let lamports = account.lamports();
+        **receiver.try_borrow_mut_lamports()? = receiver
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **account.try_borrow_mut_lamports()? = 0;
+
+        // Zeroing, shrinking and handing the account back to the system program means a
+        // lamport top-up later in the same transaction cannot revive the old record.
+        account.try_borrow_mut_data()?.fill(0);
+        account.realloc(0, false)?;
+        account.assign(&system_program::id());
+
+        Ok(())
+    }
+
+    pub(crate) fn process_ownership_account(
         ownership_account: &AccountInfo,
         program_id: &Pubkey,
         plant_name: &str,
         card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
         user_wallet_account: &AccountInfo,
     ) -> Result<(Pubkey, u8), ProgramError> {
-        let (ownership_pda, ownership_bump) = Pubkey::find_program_address(
-            &[
-                plant_name.as_bytes(),
-                user_wallet_account.key.as_ref(),
-                &[card_type.clone() as u8],
-            ],
+        let (ownership_pda, ownership_bump) = find_ownership_address(
             program_id,
+            plant_name,
+            user_wallet_account.key,
+            card_type,
+            quiz_id,
         );
 
         if ownership_pda != *ownership_account.key {
@@ -83,7 +193,7 @@ impl Processor {
 
         if ownership_account.lamports() > 0 {
             msg!("User already owns this card for plant: {}", plant_name);
-            return Err(ProgramError::Custom(999));
+            return Err(BiodexError::CardAlreadyOwned.into());
         }
 
         Ok((ownership_pda, ownership_bump))
@@ -118,7 +228,11 @@ impl Processor {
                 common_count: 0,
                 mastery_count: 0,
                 codex_count: 0,
+                burned_count: 0,
                 first_minter: None,
+                plant_id: 0,
+                fused_count: 0,
+                discovery: DiscoveryState::Undiscovered,
             })
         } else {
             Ok(PlantCounter::try_from_slice(
@@ -128,8 +242,7 @@ impl Processor {
     }
 
     fn determine_rarity<'a>(
-        is_first_on_chain: bool,
-        is_new_species: bool,
+        first_discovery: Option<DiscoveryKind>,
         counter: &mut PlantCounter,
         user_wallet: &Pubkey,
         common_mint: &'a AccountInfo<'a>,
@@ -137,28 +250,39 @@ impl Processor {
         epic_mint: &'a AccountInfo<'a>,
         primordial_mint: &'a AccountInfo<'a>,
         aurora_mint_account: &'a AccountInfo<'a>,
+        rules: &RegularRules<'a>,
     ) -> (CardRarityInstruction, &'a AccountInfo<'a>) {
-        if is_new_species && is_first_on_chain && counter.seed_count == 0 {
+        if first_discovery == Some(DiscoveryKind::NewSpecies) {
             msg!("AuroraSeed AWARDED!");
             msg!("This is a brand new species discovery!");
             counter.first_minter = Some(*user_wallet);
             counter.seed_count += 1;
             (CardRarityInstruction::AuroraSeed, aurora_mint_account)
-        } else if !is_new_species && is_first_on_chain && counter.relic_count == 0 {
+        } else if first_discovery == Some(DiscoveryKind::KnownSpecies) {
             msg!("PrimordialRelic AWARDED!");
             msg!("First person to photograph this known plant!");
             counter.relic_count += 1;
             counter.first_minter = Some(*user_wallet);
             (CardRarityInstruction::PrimordialRelic, primordial_mint)
+        } else if let Some(regional_relic_mint) = rules.regional_relic_mint {
+            msg!("RegionalRelic AWARDED!");
+            msg!("First person to photograph this plant in the region!");
+            (CardRarityInstruction::RegionalRelic, regional_relic_mint)
         } else {
             msg!("Regular rarity distribution");
 
-            if counter.epic_count < MAX_EPIC {
+            let allow_epic = rules.ceiling == CardRarityInstruction::MythicCrest;
+            let allow_rare = allow_epic || rules.ceiling == CardRarityInstruction::AstralShard;
+
+            if allow_epic && counter.epic_count < rules.max_epic {
                 counter.epic_count += 1;
                 (CardRarityInstruction::MythicCrest, epic_mint)
-            } else if counter.rare_count < MAX_RARE {
+            } else if allow_rare && counter.rare_count < rules.max_rare {
                 counter.rare_count += 1;
                 (CardRarityInstruction::AstralShard, rare_mint)
+            } else if let Some(bloom_mint) = rules.bloom_mint {
+                msg!("SeasonalBloom AWARDED!");
+                (CardRarityInstruction::SeasonalBloom, bloom_mint)
             } else {
                 counter.common_count += 1;
                 (CardRarityInstruction::GenesisFragment, common_mint)
@@ -166,7 +290,7 @@ impl Processor {
         }
     }
 
-    fn ensure_associated_token_account<'a>(
+    pub(crate) fn ensure_associated_token_account<'a>(
         associated_token_account: &AccountInfo<'a>,
         payer: &AccountInfo<'a>,
         user_wallet: &AccountInfo<'a>,
@@ -204,19 +328,49 @@ impl Processor {
         Ok(())
     }
 
-    fn nft_mint<'a>(
+    // `mint_authority` is the mint authority PDA, which signs every token CPI here.
+    pub(crate) fn nft_mint<'a>(
+        program_id: &Pubkey,
         token_program: &AccountInfo<'a>,
         mint_account: &AccountInfo<'a>,
         associated_token_account: &AccountInfo<'a>,
         mint_authority: &AccountInfo<'a>,
+        soulbound: bool,
     ) -> ProgramResult {
-        invoke(
+        let mint_authority_bump = check_mint_authority(program_id, mint_authority)?;
+        let signer_seeds: &[&[u8]] = &[b"mint_authority", &[mint_authority_bump]];
+        let freeze_accounts = [
+            associated_token_account.clone(),
+            mint_account.clone(),
+            mint_authority.clone(),
+            token_program.clone(),
+        ];
+
+        // A wallet holding an earlier soulbound card of this mint has a frozen account.
+        if StateWithExtensions::<TokenAccount>::unpack(&associated_token_account.data.borrow())?
+            .base
+            .is_frozen()
+        {
+            invoke_signed(
+                &token_instruction::thaw_account(
+                    token_program.key,
+                    associated_token_account.key,
+                    mint_account.key,
+                    mint_authority.key,
+                    &[],
+                )?,
+                &freeze_accounts,
+                &[signer_seeds],
+            )?;
+        }
+
+        invoke_signed(
             &token_instruction::mint_to(
                 token_program.key,
                 mint_account.key,
                 associated_token_account.key,
                 mint_authority.key,
-                &[mint_authority.key],
+                &[],
                 1,
             )?,
             &[
@@ -225,54 +379,59 @@ impl Processor {
                 associated_token_account.clone(),
                 token_program.clone(),
             ],
+            &[signer_seeds],
         )?;
 
+        if soulbound {
+            msg!("Freezing soulbound card");
+            invoke_signed(
+                &token_instruction::freeze_account(
+                    token_program.key,
+                    associated_token_account.key,
+                    mint_account.key,
+                    mint_authority.key,
+                    &[],
+                )?,
+                &freeze_accounts,
+                &[signer_seeds],
+            )?;
+        }
+
         Ok(())
     }
 
-    fn create_ownership_record<'a>(
+    pub(crate) fn create_ownership_record<'a>(
         payer: &AccountInfo<'a>,
         ownership_account: &AccountInfo<'a>,
         system_program: &AccountInfo<'a>,
         program_id: &Pubkey,
-        user_wallet: &Pubkey,
-        plant_name: &str,
-        card_type: CardRarityInstruction,
-        mint_account: &Pubkey,
+        record: &OwnershipRecord,
         ownership_bump: u8,
     ) -> ProgramResult {
-        let ownership_record = borsh::to_vec(&OwnershipRecord::new(
-            *user_wallet,
-            plant_name.to_string(),
-            card_type.clone(),
-            *mint_account,
-        ))?;
-        let ownership_space = ownership_record.len() as u64;
-        let rent_lamports = Rent::get()?.minimum_balance(ownership_space as usize) as usize;
+        let ownership_record = borsh::to_vec(record)?;
+        let card_seed = [record.rarity.clone() as u8];
+        let quiz_seed = record.quiz_id.map(u64::to_le_bytes);
+        let bump_seed = [ownership_bump];
+        let mut signer_seeds = ownership_seeds(
+            &record.plant_name,
+            &record.owner,
+            &card_seed,
+            quiz_seed.as_ref(),
+        );
+        signer_seeds.push(&bump_seed);
 
-        invoke_signed(
-            &system_instruction::create_account(
-                &payer.key,
-                &ownership_account.key,
-                Rent::get()?.minimum_balance(rent_lamports),
-                ownership_space,
-                program_id,
-            ),
-            &[
-                payer.clone(),
-                system_program.clone(),
-                ownership_account.clone(),
-            ],
-            &[&[
-                plant_name.as_bytes(),
-                user_wallet.as_ref(),
-                &[card_type.clone() as u8],
-                &[ownership_bump],
-            ]],
+        Self::create_pda_account(
+            payer,
+            ownership_account,
+            system_program,
+            program_id,
+            ownership_record.len(),
+            &signer_seeds,
         )?;
 
-        let mut ownership_acc_mut = ownership_account.try_borrow_mut_data()?;
-        ownership_acc_mut[..ownership_record.len()].copy_from_slice(&ownership_record);
+        ownership_account
+            .try_borrow_mut_data()?
+            .copy_from_slice(&ownership_record);
 
         Ok(())
     }
@@ -291,8 +450,6 @@ impl Processor {
             let required_space = serialized_counter.len();
             let rent_lamports = Rent::get()?.minimum_balance(required_space);
 
-            msg!("Inside the first timer");
-
             invoke_signed(
                 &system_instruction::create_account(
                     payer.key,
@@ -313,7 +470,6 @@ impl Processor {
                 ]],
             )?;
         }
-        msg!("Outside becasue saved");
 
         let mut data = plant_counter_account.try_borrow_mut_data()?;
         data[..serialized_counter.len()].copy_from_slice(&serialized_counter);
@@ -321,12 +477,15 @@ impl Processor {
         Ok(())
     }
 
-    fn process_minting<'a>(
+    // `regular_ceiling` is only set by SettleMint, with the tier drawn from the rarity odds.
+    pub(crate) fn process_minting<'a>(
         program_id: &Pubkey,
         accounts: &'a [AccountInfo<'a>],
-        card_type: CardRarityInstruction,
         plant_name: &str,
         is_new_species: Option<bool>,
+        regular_ceiling: Option<CardRarityInstruction>,
+        context: Option<DiscoveryContext>,
+        trailing_accounts: &'a [AccountInfo<'a>],
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
 
@@ -336,7 +495,7 @@ impl Processor {
         let epic_mint_account = next_account_info(accounts_iter)?;
         let aurora_mint_account = next_account_info(accounts_iter)?;
         let primordial_mint_account = next_account_info(accounts_iter)?;
-        let mint_authority = next_account_info(accounts_iter)?;
+        let attestation_oracle = next_account_info(accounts_iter)?;
         let associated_token_account = next_account_info(accounts_iter)?;
         let payer = next_account_info(accounts_iter)?;
         let rent = next_account_info(accounts_iter)?;
@@ -344,18 +503,120 @@ impl Processor {
         let token_program = next_account_info(accounts_iter)?;
         let ownership_account = next_account_info(accounts_iter)?;
         let plant_counter_account = next_account_info(accounts_iter)?;
+        let config_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let plant_registry_account = next_account_info(accounts_iter)?;
+        let collection_page_account = next_account_info(accounts_iter)?;
+        let role_registry_account = next_account_info(accounts_iter)?;
+        let rate_limit_account = next_account_info(accounts_iter)?;
+        let mint_authority = next_account_info(accounts_iter)?;
+
+        check_token_program(token_program)?;
+
+        // The context is only trusted because an attestation oracle co-signs every mint.
+        require_role(
+            program_id,
+            role_registry_account,
+            attestation_oracle,
+            Role::AttestationOracle,
+        )?;
+        if context.as_ref().is_some_and(|context| !context.is_valid()) {
+            msg!(
+                "Discovery context geohash must be at most {} base32 characters",
+                MAX_GEOHASH_LEN
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let config = load_config_or_default(program_id, config_account)?;
+        require_not_paused(&config, PauseScope::Discovery)?;
+        record_mint(
+            program_id,
+            &config,
+            rate_limit_account,
+            payer,
+            system_program,
+            user_wallet_account.key,
+        )?;
 
         let (_plant_counter_pda, _plant_counter_bump) =
             Self::process_plant_counter_pda(plant_name, program_id, plant_counter_account)?;
 
         let is_first_on_chain = plant_counter_account.data_is_empty();
-
         let mut counter = Self::load_or_init_counter(plant_counter_account, plant_name)?;
+        if is_first_on_chain {
+            counter.plant_id =
+                assign_plant_id(program_id, plant_registry_account, payer, system_program)?;
+        }
+
+        let first_discovery = counter.discovery.discover(
+            is_new_species.unwrap(),
+            *user_wallet_account.key,
+            Clock::get()?.unix_timestamp,
+        );
+        if config.rarity_odds.is_some() && first_discovery.is_none() && regular_ceiling.is_none() {
+            msg!("Rarity odds are enabled; use RequestMint and SettleMint for known plants");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (season_accounts, region_accounts) = if config.active_season.is_some() {
+            trailing_accounts.split_at(SEASON_ACCOUNT_COUNT.min(trailing_accounts.len()))
+        } else {
+            (&[][..], trailing_accounts)
+        };
+
+        let mut season =
+            load_active_season(program_id, &config, season_accounts.first(), plant_name)?;
+        let bloom_mint = match &season {
+            Some(season) if season.blooms_left() => {
+                let bloom_mint = season_accounts
+                    .get(1)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                if *bloom_mint.key != season.bloom_mint {
+                    msg!("Bloom mint does not match the season");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                Some(bloom_mint)
+            }
+            _ => None,
+        };
+        let mut region = match context.as_ref().and_then(|context| context.region_id) {
+            Some(region_id) => Some(load_region(
+                program_id,
+                plant_name,
+                region_id,
+                region_accounts,
+            )?),
+            None => None,
+        };
+        // A user holds one RegionalRelic per plant, so a second region stays open for others.
+        let regional_relic_mint = match &region {
+            Some(region)
+                if region.counter.first_minter.is_none()
+                    && !holds_card(
+                        program_id,
+                        collection_page_account,
+                        user_wallet_account.key,
+                        counter.plant_id,
+                        &CardRarityInstruction::RegionalRelic,
+                    )? =>
+            {
+                Some(region.relic_mint)
+            }
+            _ => None,
+        };
+
+        let rules = RegularRules {
+            ceiling: regular_ceiling.unwrap_or(CardRarityInstruction::MythicCrest),
+            max_epic: MAX_EPIC + season.as_ref().map_or(0, |season| season.epic_cap_bonus),
+            max_rare: MAX_RARE + season.as_ref().map_or(0, |season| season.rare_cap_bonus),
+            bloom_mint,
+            regional_relic_mint,
+        };
 
         // FIRST: Determine what rarity will actually be minted
         let (final_rarity, mint_account) = Self::determine_rarity(
-            is_first_on_chain,
-            is_new_species.unwrap(),
+            first_discovery,
             &mut counter,
             user_wallet_account.key,
             common_mint_account,
@@ -363,19 +624,48 @@ impl Processor {
             epic_mint_account,
             primordial_mint_account,
             aurora_mint_account,
+            &rules,
         );
 
         msg!("Final rarity: {:?}", final_rarity);
 
-        // THEN: Check ownership using the FINAL rarity, not the input card_type
-        let (_ownership_pda, ownership_bump) = Self::process_ownership_account(
-            ownership_account,
+        // THEN: Check ownership using the FINAL rarity, not the input card_type.
+        // In merkle log mode the ownership slot carries the ownership tree instead, so the
+        // collection page is what stops a user from minting the same card twice.
+        let ownership_bump = if config.merkle_log {
+            if holds_card(
+                program_id,
+                collection_page_account,
+                user_wallet_account.key,
+                counter.plant_id,
+                &final_rarity,
+            )? {
+                msg!("User already owns this card for plant: {}", plant_name);
+                return Err(BiodexError::CardAlreadyOwned.into());
+            }
+            None
+        } else {
+            let (_ownership_pda, ownership_bump) = Self::process_ownership_account(
+                ownership_account,
+                program_id,
+                plant_name,
+                final_rarity.clone(),
+                None,
+                user_wallet_account,
+            )
+            .unwrap();
+            Some(ownership_bump)
+        };
+
+        add_card(
             program_id,
-            plant_name,
-            final_rarity.clone(),
-            user_wallet_account,
-        )
-        .unwrap();
+            collection_page_account,
+            payer,
+            system_program,
+            user_wallet_account.key,
+            counter.plant_id,
+            &final_rarity,
+        )?;
 
         msg!("Minting {:?} card for plant {}", final_rarity, plant_name);
 
@@ -391,24 +681,41 @@ impl Processor {
 
         msg!("Minting NFT to associated token account...");
         Self::nft_mint(
+            program_id,
             token_program,
             mint_account,
             associated_token_account,
             mint_authority,
+            config.is_soulbound(&final_rarity),
         )?;
         msg!("NFT minted successfully");
 
-        Self::create_ownership_record(
-            payer,
-            ownership_account,
-            system_program,
-            program_id,
-            user_wallet_account.key,
-            plant_name,
-            final_rarity.clone(),
-            mint_account.key,
-            ownership_bump,
-        )?;
+        match ownership_bump {
+            Some(ownership_bump) => Self::create_ownership_record(
+                payer,
+                ownership_account,
+                system_program,
+                program_id,
+                &OwnershipRecord {
+                    context,
+                    ..OwnershipRecord::new(
+                        *user_wallet_account.key,
+                        plant_name.to_string(),
+                        final_rarity.clone(),
+                        *mint_account.key,
+                        None,
+                    )
+                },
+                ownership_bump,
+            )?,
+            None => append_ownership_leaf(
+                program_id,
+                ownership_account,
+                user_wallet_account.key,
+                plant_name,
+                &final_rarity,
+            )?,
+        }
 
         Self::save_plant_counter(
             plant_counter_account,
@@ -420,14 +727,153 @@ impl Processor {
             _plant_counter_bump,
         )?;
 
+        record_card(
+            program_id,
+            profile_account,
+            payer,
+            system_program,
+            user_wallet_account.key,
+            plant_name,
+            &final_rarity,
+        )?;
+
+        if final_rarity == CardRarityInstruction::SeasonalBloom {
+            if let (Some(season), Some(season_account)) = (season.as_mut(), season_accounts.first())
+            {
+                season.blooms_minted += 1;
+                save_season(season_account, season)?;
+            }
+        }
+
+        if let Some(region) = region.as_mut() {
+            // The global first discoverer also opens the region, without a second relic.
+            if region.counter.first_minter.is_none()
+                && matches!(
+                    final_rarity,
+                    CardRarityInstruction::AuroraSeed
+                        | CardRarityInstruction::PrimordialRelic
+                        | CardRarityInstruction::RegionalRelic
+                )
+            {
+                region.counter.first_minter = Some(*user_wallet_account.key);
+            }
+            region.counter.card_count += 1;
+            save_region(program_id, region, payer, system_program)?;
+
+            if final_rarity == CardRarityInstruction::RegionalRelic {
+                emit(&BiodexEvent::RegionFirstDiscovered {
+                    plant_name: plant_name.to_string(),
+                    region_id: region.counter.region_id,
+                    discoverer: *user_wallet_account.key,
+                });
+            }
+        }
+
+        if matches!(
+            final_rarity,
+            CardRarityInstruction::AuroraSeed | CardRarityInstruction::PrimordialRelic
+        ) {
+            emit(&BiodexEvent::PlantFirstDiscovered {
+                plant_name: plant_name.to_string(),
+                discoverer: *user_wallet_account.key,
+                new_species: final_rarity == CardRarityInstruction::AuroraSeed,
+            });
+        }
+        emit(&BiodexEvent::CardMinted {
+            owner: *user_wallet_account.key,
+            plant_name: plant_name.to_string(),
+            rarity: final_rarity,
+            mint: *mint_account.key,
+            quiz_id: None,
+        });
+
         Ok(())
     }
 
-    fn process_quiz(
+    pub fn process_quiz_submission<'a>(
         program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        card_type: CardRarityInstruction,
+        accounts: &'a [AccountInfo<'a>],
+        quiz_id: u64,
+        answers: &[u8],
+        salt: &[u8; 32],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        let quiz_account = next_account_info(accounts_iter)?;
+        let quiz_result_account = next_account_info(accounts_iter)?;
+        let user_wallet_account = next_account_info(accounts_iter)?;
+
+        if !user_wallet_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut quiz = load_quiz(program_id, quiz_account, quiz_id)?;
+        let answer_key = match &quiz.answers {
+            Some(answer_key) => answer_key.clone(),
+            None => {
+                msg!("Quiz {} has not been settled yet", quiz_id);
+                return Err(BiodexError::QuizNotSettled.into());
+            }
+        };
+        if Clock::get()?.unix_timestamp > quiz.claims_close_at {
+            msg!("Claims for quiz {} have closed", quiz_id);
+            return Err(BiodexError::QuizClaimsClosed.into());
+        }
+
+        let (quiz_result_pda, _) =
+            find_quiz_result_address(program_id, quiz_id, user_wallet_account.key);
+        if quiz_result_pda != *quiz_result_account.key || quiz_result_account.owner != program_id {
+            msg!("No answer commitment found for this participant");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut quiz_result = QuizResult::try_from_slice(&quiz_result_account.data.borrow())?;
+
+        if quiz_result.outcome != QuizOutcome::Pending {
+            msg!("User already claimed quiz {}", quiz_id);
+            return Err(BiodexError::QuizAlreadySubmitted.into());
+        }
+
+        let revealed = participant_commitment(user_wallet_account.key, answers, salt);
+        if !constant_time_eq(&revealed, &quiz_result.commitment) {
+            msg!("Revealed answers do not match the commitment");
+            return Err(BiodexError::CommitmentMismatch.into());
+        }
+
+        let is_winner =
+            constant_time_eq(answers, &answer_key) && quiz.winner_count < quiz.max_winners;
+
+        quiz.claimed_count += 1;
+        if is_winner {
+            quiz.winner_count += 1;
+            quiz_result.outcome = QuizOutcome::Winner;
+        } else {
+            quiz_result.outcome = QuizOutcome::Participant;
+        }
+
+        let serialized_quiz = borsh::to_vec(&quiz)?;
+        quiz_account
+            .try_borrow_mut_data()?
+            .copy_from_slice(&serialized_quiz);
+
+        let serialized_result = borsh::to_vec(&quiz_result)?;
+        quiz_result_account
+            .try_borrow_mut_data()?
+            .copy_from_slice(&serialized_result);
+
+        Self::process_quiz(
+            program_id,
+            &accounts[2..],
+            &quiz.plant_name,
+            quiz_id,
+            is_winner,
+        )
+    }
+
+    fn process_quiz<'a>(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
         plant_name: &str,
+        quiz_id: u64,
         is_winner: bool,
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
@@ -443,20 +889,33 @@ impl Processor {
         let token_program = next_account_info(accounts_iter)?;
         let ownership_account = next_account_info(accounts_iter)?;
         let plant_counter_account = next_account_info(accounts_iter)?;
+        let config_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let rate_limit_account = next_account_info(accounts_iter)?;
+        let plant_registry_account = next_account_info(accounts_iter)?;
 
-        let (_, ownership_bump) = Self::process_ownership_account(
-            ownership_account,
+        check_token_program(token_program)?;
+
+        let config = load_config_or_default(program_id, config_account)?;
+        require_not_paused(&config, PauseScope::Quizzes)?;
+        record_mint(
             program_id,
-            plant_name,
-            card_type.clone(),
-            user_wallet_account,
-        )
-        .unwrap();
+            &config,
+            rate_limit_account,
+            payer,
+            system_program,
+            user_wallet_account.key,
+        )?;
 
-        let (_plant_counter_pda, _plant_counter_bump) =
-            Self::process_plant_counter_pda(plant_name, program_id, plant_counter_account).unwrap();
+        let (_plant_counter_pda, plant_counter_bump) =
+            Self::process_plant_counter_pda(plant_name, program_id, plant_counter_account)?;
 
+        let is_first_on_chain = plant_counter_account.data_is_empty();
         let mut counter = Self::load_or_init_counter(plant_counter_account, plant_name)?;
+        if is_first_on_chain {
+            counter.plant_id =
+                assign_plant_id(program_id, plant_registry_account, payer, system_program)?;
+        }
 
         let (final_card, mint_account) = if is_winner {
             msg!("AscendantSeal AWARDED - Quiz Winner!");
@@ -468,6 +927,20 @@ impl Processor {
             (CardRarityInstruction::CodexOfInsight, codex_mint_account)
         };
 
+        let ownership_bump = if config.merkle_log {
+            None
+        } else {
+            let (_, ownership_bump) = Self::process_ownership_account(
+                ownership_account,
+                program_id,
+                plant_name,
+                final_card.clone(),
+                Some(quiz_id),
+                user_wallet_account,
+            )?;
+            Some(ownership_bump)
+        };
+
         Self::ensure_associated_token_account(
             associated_token_account,
             payer,
@@ -478,30 +951,74 @@ impl Processor {
             rent,
         )?;
 
-        msg!("Associated Token Address: {}", associated_token_account.key);
-
         msg!("Minting NFT to associated token account...");
         Self::nft_mint(
+            program_id,
             token_program,
             mint_account,
             associated_token_account,
             mint_authority,
+            config.is_soulbound(&final_card),
         )?;
         msg!("NFT minted successfully");
 
-        Self::create_ownership_record(
+        match ownership_bump {
+            Some(ownership_bump) => Self::create_ownership_record(
+                payer,
+                ownership_account,
+                system_program,
+                program_id,
+                &OwnershipRecord::new(
+                    *user_wallet_account.key,
+                    plant_name.to_string(),
+                    final_card.clone(),
+                    *mint_account.key,
+                    Some(quiz_id),
+                ),
+                ownership_bump,
+            )?,
+            None => append_ownership_leaf(
+                program_id,
+                ownership_account,
+                user_wallet_account.key,
+                plant_name,
+                &final_card,
+            )?,
+        }
+
+        Self::save_plant_counter(
+            plant_counter_account,
+            &counter,
             payer,
-            ownership_account,
             system_program,
             program_id,
+            plant_name,
+            plant_counter_bump,
+        )?;
+
+        record_card(
+            program_id,
+            profile_account,
+            payer,
+            system_program,
             user_wallet_account.key,
             plant_name,
-            final_card,
-            mint_account.key,
-            ownership_bump,
+            &final_card,
         )?;
 
-        Self::process_plant_counter_pda(plant_name, program_id, plant_counter_account)?;
+        emit(&BiodexEvent::QuizCardAwarded {
+            quiz_id,
+            participant: *user_wallet_account.key,
+            plant_name: plant_name.to_string(),
+            rarity: final_card.clone(),
+        });
+        emit(&BiodexEvent::CardMinted {
+            owner: *user_wallet_account.key,
+            plant_name: plant_name.to_string(),
+            rarity: final_card,
+            mint: *mint_account.key,
+            quiz_id: Some(quiz_id),
+        });
 
         Ok(())
     }
@@ -0,0 +1,148 @@
+use {
+    crate::{
+        instruction::{CardRarityInstruction, UserProfile, CARD_RARITY_COUNT},
+        processor::Processor,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, hash::hash, msg,
+        program_error::ProgramError, pubkey::Pubkey,
+    },
+};
+
+pub fn find_user_profile_address(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"user_profile", user.as_ref()], program_id)
+}
+
+pub fn plant_hash(plant_name: &str) -> [u8; 32] {
+    hash(plant_name.as_bytes()).to_bytes()
+}
+
+fn load_profile(
+    program_id: &Pubkey,
+    profile_account: &AccountInfo,
+    user: &Pubkey,
+) -> Result<UserProfile, ProgramError> {
+    let (profile_pda, _) = find_user_profile_address(program_id, user);
+    if profile_pda != *profile_account.key {
+        msg!("User profile account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if profile_account.data_is_empty() {
+        return Ok(UserProfile {
+            user: *user,
+            cards_by_rarity: [0; CARD_RARITY_COUNT],
+            first_discoveries: 0,
+            quiz_wins: 0,
+            plants: Vec::new(),
+        });
+    }
+
+    if profile_account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(UserProfile::try_from_slice(&profile_account.data.borrow())?)
+}
+
+fn save_profile<'a>(
+    program_id: &Pubkey,
+    profile_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    profile: &UserProfile,
+) -> ProgramResult {
+    let serialized_profile = borsh::to_vec(profile)?;
+    if profile_account.data_is_empty() {
+        let (_, profile_bump) = find_user_profile_address(program_id, &profile.user);
+        Processor::create_pda_account(
+            payer,
+            profile_account,
+            system_program,
+            program_id,
+            serialized_profile.len(),
+            &[b"user_profile", profile.user.as_ref(), &[profile_bump]],
+        )?;
+    }
+
+    Processor::write_account_data(profile_account, &serialized_profile, payer, system_program)
+}
+
+fn add_held_card(profile: &mut UserProfile, plant_name: &str, rarity: &CardRarityInstruction) {
+    profile.cards_by_rarity[rarity.clone() as usize] += 1;
+    let plant = plant_hash(plant_name);
+    if !profile.plants.contains(&plant) {
+        profile.plants.push(plant);
+    }
+}
+
+// Called from the mint paths after the card is minted, so the profile always
+// moves together with the ownership record.
+pub(crate) fn record_card<'a>(
+    program_id: &Pubkey,
+    profile_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    user: &Pubkey,
+    plant_name: &str,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    let mut profile = load_profile(program_id, profile_account, user)?;
+
+    add_held_card(&mut profile, plant_name, rarity);
+    match rarity {
+        CardRarityInstruction::AuroraSeed | CardRarityInstruction::PrimordialRelic => {
+            profile.first_discoveries += 1
+        }
+        CardRarityInstruction::AscendantSeal => profile.quiz_wins += 1,
+        _ => {}
+    }
+
+    save_profile(program_id, profile_account, payer, system_program, &profile)
+}
+
+// Credits a card that changed hands. First discoveries and quiz wins stay with whoever
+// earned them.
+pub(crate) fn receive_card<'a>(
+    program_id: &Pubkey,
+    profile_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    user: &Pubkey,
+    plant_name: &str,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    let mut profile = load_profile(program_id, profile_account, user)?;
+    add_held_card(&mut profile, plant_name, rarity);
+    save_profile(program_id, profile_account, payer, system_program, &profile)
+}
+
+// Counterpart of `record_card` for burned, transferred and fused cards. Profiles created
+// before they tracked a card are left at zero rather than failing the instruction.
+pub(crate) fn remove_card(
+    program_id: &Pubkey,
+    profile_account: &AccountInfo,
+    user: &Pubkey,
+    rarity: &CardRarityInstruction,
+) -> ProgramResult {
+    if profile_account.data_is_empty() {
+        let (profile_pda, _) = find_user_profile_address(program_id, user);
+        if profile_pda != *profile_account.key {
+            msg!("User profile account does not match derived PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+        return Ok(());
+    }
+
+    let mut profile = load_profile(program_id, profile_account, user)?;
+    let held = &mut profile.cards_by_rarity[rarity.clone() as usize];
+    *held = held.saturating_sub(1);
+
+    // Only a count changes, so the profile keeps its size.
+    let serialized_profile = borsh::to_vec(&profile)?;
+    profile_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_profile);
+
+    Ok(())
+}
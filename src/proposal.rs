@@ -0,0 +1,273 @@
+use {
+    crate::{
+        config::load_config,
+        error::BiodexError,
+        instruction::{Multisig, ProgramInstruction, Proposal, ProposalAccount},
+        processor::Processor,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        msg,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub const MAX_APPROVERS: usize = 10;
+
+pub fn find_proposal_address(program_id: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"proposal", &proposal_id.to_le_bytes()], program_id)
+}
+
+// Signs executed proposals. Make it the config admin, or grant it roles, to put those
+// instructions behind the multisig.
+pub fn find_multisig_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"multisig_authority"], program_id)
+}
+
+fn require_approver(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    approver: &AccountInfo,
+) -> Result<Multisig, ProgramError> {
+    let Some(multisig) = load_config(program_id, config_account)?.multisig else {
+        msg!("No multisig is configured");
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    if !approver.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !multisig.is_approver(approver.key) {
+        msg!("Signer is not a multisig approver");
+        return Err(BiodexError::Unauthorized.into());
+    }
+
+    Ok(multisig)
+}
+
+fn load_proposal(
+    program_id: &Pubkey,
+    proposal_account: &AccountInfo,
+    proposal_id: u64,
+) -> Result<Proposal, ProgramError> {
+    let (proposal_pda, _) = find_proposal_address(program_id, proposal_id);
+    if proposal_pda != *proposal_account.key || proposal_account.owner != program_id {
+        msg!("Proposal account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let proposal = Proposal::try_from_slice(&proposal_account.data.borrow())?;
+    if proposal.executed {
+        msg!("Proposal {} was already executed", proposal_id);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(proposal)
+}
+
+// Records the approval and starts the timelock once the threshold is met.
+fn record_approval(
+    proposal: &mut Proposal,
+    multisig: &Multisig,
+    approver: Pubkey,
+) -> ProgramResult {
+    if !proposal.approvals.contains(&approver) {
+        proposal.approvals.push(approver);
+    }
+
+    if proposal.ready_at.is_none()
+        && multisig.approval_count(&proposal.approvals) >= multisig.threshold as usize
+    {
+        let ready_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(multisig.timelock)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        msg!(
+            "Proposal {} can run from {}",
+            proposal.proposal_id,
+            ready_at
+        );
+        proposal.ready_at = Some(ready_at);
+    }
+
+    Ok(())
+}
+
+pub fn create_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+    instruction: Vec<u8>,
+    instruction_accounts: Vec<ProposalAccount>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let proposer = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let proposal_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    let multisig = require_approver(program_id, config_account, proposer)?;
+
+    if ProgramInstruction::try_from_slice(&instruction).is_err() {
+        msg!("Proposal does not hold a valid instruction");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (proposal_pda, proposal_bump) = find_proposal_address(program_id, proposal_id);
+    if proposal_pda != *proposal_account.key {
+        msg!("Proposal account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if proposal_account.lamports() > 0 {
+        msg!("Proposal {} already exists", proposal_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The proposer approves their own proposal.
+    let mut proposal = Proposal {
+        proposal_id,
+        proposer: *proposer.key,
+        instruction,
+        accounts: instruction_accounts,
+        approvals: Vec::new(),
+        ready_at: None,
+        executed: false,
+    };
+    record_approval(&mut proposal, &multisig, *proposer.key)?;
+    let proposal = borsh::to_vec(&proposal)?;
+
+    msg!("Creating proposal {}...", proposal_id);
+    Processor::create_pda_account(
+        payer,
+        proposal_account,
+        system_program,
+        program_id,
+        proposal.len(),
+        &[b"proposal", &proposal_id.to_le_bytes(), &[proposal_bump]],
+    )?;
+    proposal_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&proposal);
+
+    Ok(())
+}
+
+pub fn approve_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let approver = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let proposal_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    let multisig = require_approver(program_id, config_account, approver)?;
+    let mut proposal = load_proposal(program_id, proposal_account, proposal_id)?;
+
+    msg!("{} approves proposal {}", approver.key, proposal_id);
+    record_approval(&mut proposal, &multisig, *approver.key)?;
+
+    Processor::write_account_data(
+        proposal_account,
+        &borsh::to_vec(&proposal)?,
+        payer,
+        system_program,
+    )
+}
+
+pub fn execute_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let config_account = next_account_info(accounts_iter)?;
+    let proposal_account = next_account_info(accounts_iter)?;
+    let authority_account = next_account_info(accounts_iter)?;
+    let program_account = next_account_info(accounts_iter)?;
+    let instruction_accounts = accounts_iter.as_slice();
+
+    let Some(multisig) = load_config(program_id, config_account)?.multisig else {
+        msg!("No multisig is configured");
+        return Err(ProgramError::InvalidAccountData);
+    };
+    let mut proposal = load_proposal(program_id, proposal_account, proposal_id)?;
+
+    // Re-checked here in case approvers were removed after the threshold was met.
+    let now = Clock::get()?.unix_timestamp;
+    if multisig.approval_count(&proposal.approvals) < multisig.threshold as usize
+        || !matches!(proposal.ready_at, Some(ready_at) if now >= ready_at)
+    {
+        msg!(
+            "Proposal {} lacks approvals or is still timelocked",
+            proposal_id
+        );
+        return Err(BiodexError::ProposalNotReady.into());
+    }
+
+    let (authority_pda, authority_bump) = find_multisig_authority_address(program_id);
+    if authority_pda != *authority_account.key {
+        msg!("Multisig authority does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if program_account.key != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // The executor only supplies the accounts; they must be the ones approvers signed off on.
+    if instruction_accounts.len() != proposal.accounts.len()
+        || instruction_accounts
+            .iter()
+            .zip(&proposal.accounts)
+            .any(|(account, meta)| {
+                *account.key != meta.pubkey || account.is_writable != meta.is_writable
+            })
+    {
+        msg!("Accounts do not match proposal {}", proposal_id);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Marked before dispatch so the instruction cannot execute its own proposal again.
+    proposal.executed = true;
+    proposal_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&borsh::to_vec(&proposal)?);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: proposal
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: proposal.instruction,
+    };
+
+    msg!("Executing proposal {}", proposal_id);
+    let mut account_infos = instruction_accounts.to_vec();
+    account_infos.push(program_account.clone());
+    invoke_signed(
+        &instruction,
+        &account_infos,
+        &[&[b"multisig_authority", &[authority_bump]]],
+    )
+}
@@ -0,0 +1,254 @@
+use {
+    crate::{
+        error::BiodexError,
+        instruction::{Quiz, QuizOutcome, QuizResult, Role},
+        processor::Processor,
+        roles::require_role,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    constant_time_eq::constant_time_eq,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hashv,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+// How long participants have to claim their card once a quiz is settled.
+pub const QUIZ_CLAIM_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct CreateQuizArgs {
+    pub quiz_id: u64,
+    pub plant_name: String,
+    pub answer_hash: [u8; 32],
+    pub opens_at: i64,
+    pub closes_at: i64,
+    pub max_winners: u64,
+}
+
+pub fn find_quiz_address(program_id: &Pubkey, quiz_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"quiz", &quiz_id.to_le_bytes()], program_id)
+}
+
+pub fn find_quiz_result_address(
+    program_id: &Pubkey,
+    quiz_id: u64,
+    participant: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"quiz_result", &quiz_id.to_le_bytes(), participant.as_ref()],
+        program_id,
+    )
+}
+
+// Committed by the quiz creator in CreateQuiz and checked again in SettleQuiz.
+pub fn answer_hash(answers: &[u8], salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[answers, salt]).to_bytes()
+}
+
+// Binding the participant key stops others from replaying someone else's commitment.
+pub fn participant_commitment(participant: &Pubkey, answers: &[u8], salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[participant.as_ref(), answers, salt]).to_bytes()
+}
+
+pub(crate) fn load_quiz(
+    program_id: &Pubkey,
+    quiz_account: &AccountInfo,
+    quiz_id: u64,
+) -> Result<Quiz, ProgramError> {
+    let (quiz_pda, _) = find_quiz_address(program_id, quiz_id);
+    if quiz_pda != *quiz_account.key || quiz_account.owner != program_id {
+        msg!("Quiz account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(Quiz::try_from_slice(&quiz_account.data.borrow())?)
+}
+
+pub fn create_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateQuizArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let authority = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+
+    require_role(
+        program_id,
+        role_registry_account,
+        authority,
+        Role::QuizMaster,
+    )?;
+
+    if args.opens_at >= args.closes_at {
+        msg!("Quiz must close after it opens");
+        return Err(BiodexError::InvalidQuizWindow.into());
+    }
+
+    let (quiz_pda, quiz_bump) = find_quiz_address(program_id, args.quiz_id);
+    if quiz_pda != *quiz_account.key {
+        msg!("Quiz account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if quiz_account.lamports() > 0 {
+        msg!("Quiz {} already exists", args.quiz_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let quiz = borsh::to_vec(&Quiz {
+        quiz_id: args.quiz_id,
+        authority: *authority.key,
+        plant_name: args.plant_name,
+        answer_hash: args.answer_hash,
+        opens_at: args.opens_at,
+        closes_at: args.closes_at,
+        max_winners: args.max_winners,
+        winner_count: 0,
+        participant_count: 0,
+        answers: None,
+        claimed_count: 0,
+        claims_close_at: 0,
+    })?;
+
+    msg!("Creating quiz {}...", args.quiz_id);
+    Processor::create_pda_account(
+        payer,
+        quiz_account,
+        system_program,
+        program_id,
+        quiz.len(),
+        &[b"quiz", &args.quiz_id.to_le_bytes(), &[quiz_bump]],
+    )?;
+
+    quiz_account.try_borrow_mut_data()?.copy_from_slice(&quiz);
+
+    Ok(())
+}
+
+pub fn commit_quiz_answers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    quiz_id: u64,
+    commitment: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let user_wallet_account = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let quiz_result_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !user_wallet_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut quiz = load_quiz(program_id, quiz_account, quiz_id)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < quiz.opens_at || now > quiz.closes_at {
+        msg!("Quiz {} is not open", quiz_id);
+        return Err(BiodexError::QuizNotOpen.into());
+    }
+
+    let (quiz_result_pda, quiz_result_bump) =
+        find_quiz_result_address(program_id, quiz_id, user_wallet_account.key);
+    if quiz_result_pda != *quiz_result_account.key {
+        msg!("Quiz result account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if quiz_result_account.lamports() > 0 {
+        msg!("User already submitted quiz {}", quiz_id);
+        return Err(BiodexError::QuizAlreadySubmitted.into());
+    }
+
+    let quiz_result = borsh::to_vec(&QuizResult {
+        quiz_id,
+        participant: *user_wallet_account.key,
+        commitment,
+        outcome: QuizOutcome::Pending,
+    })?;
+    Processor::create_pda_account(
+        payer,
+        quiz_result_account,
+        system_program,
+        program_id,
+        quiz_result.len(),
+        &[
+            b"quiz_result",
+            &quiz_id.to_le_bytes(),
+            user_wallet_account.key.as_ref(),
+            &[quiz_result_bump],
+        ],
+    )?;
+    quiz_result_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&quiz_result);
+
+    quiz.participant_count += 1;
+    let serialized_quiz = borsh::to_vec(&quiz)?;
+    quiz_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_quiz);
+
+    msg!("Answers committed for quiz {}", quiz_id);
+
+    Ok(())
+}
+
+pub fn settle_quiz(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    quiz_id: u64,
+    answers: Vec<u8>,
+    salt: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let authority = next_account_info(accounts_iter)?;
+    let quiz_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    let mut quiz = load_quiz(program_id, quiz_account, quiz_id)?;
+
+    if !authority.is_signer || *authority.key != quiz.authority {
+        msg!("Only the quiz authority can settle quiz {}", quiz_id);
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if now <= quiz.closes_at {
+        msg!("Quiz {} is still accepting commitments", quiz_id);
+        return Err(BiodexError::QuizStillOpen.into());
+    }
+
+    if quiz.answers.is_some() {
+        return Err(BiodexError::QuizAlreadySettled.into());
+    }
+
+    if !constant_time_eq(&answer_hash(&answers, &salt), &quiz.answer_hash) {
+        msg!("Answers do not match the quiz commitment");
+        return Err(BiodexError::CommitmentMismatch.into());
+    }
+
+    quiz.answers = Some(answers);
+    quiz.claims_close_at = now.saturating_add(QUIZ_CLAIM_WINDOW);
+    Processor::write_account_data(quiz_account, &borsh::to_vec(&quiz)?, payer, system_program)?;
+
+    msg!("Quiz {} settled", quiz_id);
+
+    Ok(())
+}
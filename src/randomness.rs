@@ -0,0 +1,250 @@
+use {
+    crate::{
+        collection::{check_page_account, load_plant_id},
+        config::{load_config_or_default, MAX_BPS},
+        error::BiodexError,
+        instruction::{
+            CardRarityInstruction, CollectionPage, DiscoveryContext, MintRequest, RarityOdds,
+        },
+        processor::{Processor, MINT_ACCOUNT_COUNT, MINT_CONFIG_INDEX},
+        season::load_active_season,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hashv,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{slot_hashes, Sysvar},
+    },
+};
+
+// SettleMint reads the request and SlotHashes after the MintNFT accounts, then the seasonal ones.
+const SETTLE_SEASON_INDEX: usize = MINT_ACCOUNT_COUNT + 2;
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+pub fn find_mint_request_address(
+    program_id: &Pubkey,
+    plant_name: &str,
+    user: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"mint_request", plant_name.as_bytes(), user.as_ref()],
+        program_id,
+    )
+}
+
+pub fn mint_commitment(user: &Pubkey, seed: &[u8; 32]) -> [u8; 32] {
+    hashv(&[user.as_ref(), seed]).to_bytes()
+}
+
+// Roll in [0, 10_000) from the user's seed and the first slot hash after the commit.
+pub fn draw_roll(seed: &[u8; 32], slot_hash: &[u8; 32], user: &Pubkey, plant_name: &str) -> u16 {
+    let randomness = hashv(&[seed, slot_hash, user.as_ref(), plant_name.as_bytes()]).to_bytes();
+    let mut roll = [0u8; 8];
+    roll.copy_from_slice(&randomness[..8]);
+    (u64::from_le_bytes(roll) % MAX_BPS as u64) as u16
+}
+
+pub fn draw_tier(odds: &RarityOdds, roll: u16) -> CardRarityInstruction {
+    if roll < odds.mythic_bps {
+        CardRarityInstruction::MythicCrest
+    } else if (roll as u32) < odds.mythic_bps as u32 + odds.astral_bps as u32 {
+        CardRarityInstruction::AstralShard
+    } else {
+        CardRarityInstruction::GenesisFragment
+    }
+}
+
+// Hash of the earliest slot after `commit_slot`. SlotHashes is sorted newest first and only
+// keeps recent slots, so None means the commit is older than the retained history.
+fn first_slot_hash_after(
+    slot_hashes_account: &AccountInfo,
+    commit_slot: u64,
+) -> Result<Option<[u8; 32]>, ProgramError> {
+    if *slot_hashes_account.key != slot_hashes::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let data = slot_hashes_account.data.borrow();
+    let entries = data
+        .get(8..)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .chunks_exact(SLOT_HASH_ENTRY_LEN);
+
+    let mut next_hash = None;
+    for entry in entries {
+        let (slot, hash) = entry.split_at(8);
+        if u64::from_le_bytes(slot.try_into().unwrap()) <= commit_slot {
+            return match next_hash {
+                Some(hash) => Ok(Some(hash)),
+                None => Err(BiodexError::RandomnessNotReady.into()),
+            };
+        }
+        next_hash = Some(hash.try_into().unwrap());
+    }
+
+    Ok(None)
+}
+
+pub fn request_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    commitment: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let plant_counter_account = next_account_info(accounts_iter)?;
+    let collection_page_account = next_account_info(accounts_iter)?;
+    let request_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if load_config_or_default(program_id, config_account)?
+        .rarity_odds
+        .is_none()
+    {
+        msg!("Rarity odds are disabled; use MintNFT");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // First discoveries stay deterministic and go through MintNFT.
+    let plant_id = load_plant_id(program_id, plant_counter_account, plant_name)?;
+
+    // A draw could land on any regular tier, so the user must not hold one for this plant yet.
+    check_page_account(program_id, collection_page_account, user.key, plant_id)?;
+    if !collection_page_account.data_is_empty() {
+        let page = CollectionPage::try_from_slice(&collection_page_account.data.borrow())?;
+        if [
+            CardRarityInstruction::GenesisFragment,
+            CardRarityInstruction::AstralShard,
+            CardRarityInstruction::MythicCrest,
+        ]
+        .iter()
+        .any(|rarity| page.contains(rarity, plant_id))
+        {
+            msg!("User already holds a regular card for plant {}", plant_name);
+            return Err(BiodexError::CardAlreadyOwned.into());
+        }
+    }
+
+    let (request_pda, request_bump) = find_mint_request_address(program_id, plant_name, user.key);
+    if request_pda != *request_account.key {
+        msg!("Mint request account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if request_account.lamports() > 0 {
+        msg!("A mint request for {} is already pending", plant_name);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let commit_slot = Clock::get()?.slot;
+    let request = borsh::to_vec(&MintRequest {
+        user: *user.key,
+        plant_name: plant_name.to_string(),
+        commitment,
+        commit_slot,
+    })?;
+
+    msg!(
+        "Mint request for {} committed at slot {}",
+        plant_name,
+        commit_slot
+    );
+    Processor::create_pda_account(
+        payer,
+        request_account,
+        system_program,
+        program_id,
+        request.len(),
+        &[
+            b"mint_request",
+            plant_name.as_bytes(),
+            user.key.as_ref(),
+            &[request_bump],
+        ],
+    )?;
+    request_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&request);
+
+    Ok(())
+}
+
+pub fn settle_mint<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    plant_name: &str,
+    seed: [u8; 32],
+    context: Option<DiscoveryContext>,
+) -> ProgramResult {
+    let user = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let accounts_iter = &mut accounts.iter().skip(MINT_ACCOUNT_COUNT);
+    let request_account = next_account_info(accounts_iter)?;
+    let slot_hashes_account = next_account_info(accounts_iter)?;
+
+    let (request_pda, _) = find_mint_request_address(program_id, plant_name, user.key);
+    if request_pda != *request_account.key || request_account.owner != program_id {
+        msg!("Mint request account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let request = MintRequest::try_from_slice(&request_account.data.borrow())?;
+
+    if mint_commitment(user.key, &seed) != request.commitment {
+        msg!("Seed does not match the mint request commitment");
+        return Err(BiodexError::CommitmentMismatch.into());
+    }
+
+    // Settling too late cannot be used to skip a bad draw: it always yields the lowest tier.
+    let ceiling = match first_slot_hash_after(slot_hashes_account, request.commit_slot)? {
+        Some(slot_hash) => {
+            let config = load_config_or_default(program_id, &accounts[MINT_CONFIG_INDEX])?;
+            let season = load_active_season(
+                program_id,
+                &config,
+                accounts.get(SETTLE_SEASON_INDEX),
+                plant_name,
+            )?;
+            let odds = match season {
+                Some(season) => season
+                    .odds_at(Clock::get()?.unix_timestamp, config.rarity_odds.as_ref())
+                    .or(config.rarity_odds),
+                None => config.rarity_odds,
+            };
+            let roll = draw_roll(&seed, &slot_hash, user.key, plant_name);
+            msg!("Rolled {} for plant {}", roll, plant_name);
+            match odds {
+                Some(odds) => draw_tier(&odds, roll),
+                // Odds were switched off after the request; fall back to arrival order.
+                None => CardRarityInstruction::MythicCrest,
+            }
+        }
+        None => {
+            msg!("Mint request expired; drawing the lowest tier");
+            CardRarityInstruction::GenesisFragment
+        }
+    };
+
+    Processor::process_minting(
+        program_id,
+        &accounts[..MINT_ACCOUNT_COUNT],
+        plant_name,
+        Some(false),
+        Some(ceiling),
+        context,
+        accounts.get(SETTLE_SEASON_INDEX..).unwrap_or_default(),
+    )?;
+
+    // Closed after the mint CPIs, which would otherwise see the user credited mid-instruction.
+    Processor::close_program_account(request_account, user)
+}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        error::BiodexError,
+        instruction::{MintRateLimit, ProgramConfig},
+        processor::Processor,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+        program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+    },
+};
+
+pub const RATE_LIMIT_WINDOW: i64 = 24 * 60 * 60;
+
+pub fn find_rate_limit_address(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"rate_limit", user.as_ref()], program_id)
+}
+
+// Counts one card against the user's daily limit. Without a configured limit the account is
+// only checked, so it is not created for users who never hit a limited deployment.
+pub(crate) fn record_mint<'a>(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    rate_limit_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    user: &Pubkey,
+) -> ProgramResult {
+    let (rate_limit_pda, rate_limit_bump) = find_rate_limit_address(program_id, user);
+    if rate_limit_pda != *rate_limit_account.key {
+        msg!("Rate limit account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let Some(daily_limit) = config.daily_mint_limit else {
+        return Ok(());
+    };
+
+    let is_new_rate_limit = rate_limit_account.data_is_empty();
+    let mut rate_limit = if is_new_rate_limit {
+        MintRateLimit {
+            user: *user,
+            recent_mints: Vec::new(),
+        }
+    } else {
+        if rate_limit_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+        MintRateLimit::try_from_slice(&rate_limit_account.data.borrow())?
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    rate_limit
+        .recent_mints
+        .retain(|minted_at| now - minted_at < RATE_LIMIT_WINDOW);
+    if rate_limit.recent_mints.len() >= daily_limit as usize {
+        msg!("User already minted {} cards in the last day", daily_limit);
+        return Err(BiodexError::MintRateLimited.into());
+    }
+    rate_limit.recent_mints.push(now);
+
+    let serialized_rate_limit = borsh::to_vec(&rate_limit)?;
+    if is_new_rate_limit {
+        Processor::create_pda_account(
+            payer,
+            rate_limit_account,
+            system_program,
+            program_id,
+            serialized_rate_limit.len(),
+            &[b"rate_limit", user.as_ref(), &[rate_limit_bump]],
+        )?;
+    }
+
+    Processor::write_account_data(
+        rate_limit_account,
+        &serialized_rate_limit,
+        payer,
+        system_program,
+    )
+}
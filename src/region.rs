@@ -0,0 +1,106 @@
+use {
+    crate::{instruction::RegionCounter, processor::Processor},
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+// Not "plant_counter": seeds are joined without separators, so a plant name ending in the
+// bytes of a region id would otherwise derive another plant's global counter.
+pub fn find_region_counter_address(
+    program_id: &Pubkey,
+    plant_name: &str,
+    region_id: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"region_counter",
+            plant_name.as_bytes(),
+            &region_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+// The region counter PDA and RegionalRelic mint a mint with a region id passes after the
+// seasonal accounts.
+pub(crate) struct Region<'a> {
+    pub counter: RegionCounter,
+    pub counter_account: &'a AccountInfo<'a>,
+    pub counter_bump: u8,
+    pub relic_mint: &'a AccountInfo<'a>,
+}
+
+pub(crate) fn load_region<'a>(
+    program_id: &Pubkey,
+    plant_name: &str,
+    region_id: u32,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<Region<'a>, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let counter_account = next_account_info(accounts_iter)?;
+    let relic_mint = next_account_info(accounts_iter)?;
+
+    let (counter_pda, counter_bump) =
+        find_region_counter_address(program_id, plant_name, region_id);
+    if counter_pda != *counter_account.key {
+        msg!("Region counter account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let counter = if counter_account.data_is_empty() {
+        RegionCounter {
+            plant_name: plant_name.to_string(),
+            region_id,
+            first_minter: None,
+            card_count: 0,
+        }
+    } else {
+        if counter_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+        RegionCounter::try_from_slice(&counter_account.data.borrow())?
+    };
+
+    Ok(Region {
+        counter,
+        counter_account,
+        counter_bump,
+        relic_mint,
+    })
+}
+
+pub(crate) fn save_region<'a>(
+    program_id: &Pubkey,
+    region: &Region<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let serialized_counter = borsh::to_vec(&region.counter)?;
+    if region.counter_account.data_is_empty() {
+        Processor::create_pda_account(
+            payer,
+            region.counter_account,
+            system_program,
+            program_id,
+            serialized_counter.len(),
+            &[
+                b"region_counter",
+                region.counter.plant_name.as_bytes(),
+                &region.counter.region_id.to_le_bytes(),
+                &[region.counter_bump],
+            ],
+        )?;
+    }
+    region
+        .counter_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&serialized_counter);
+
+    Ok(())
+}
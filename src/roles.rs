@@ -0,0 +1,176 @@
+use {
+    crate::{
+        config::require_admin,
+        error::BiodexError,
+        instruction::{Role, RoleGrant, RoleRegistry},
+        processor::Processor,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn find_role_registry_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"role_registry"], program_id)
+}
+
+fn load_role_registry(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo,
+) -> Result<RoleRegistry, ProgramError> {
+    let (registry_pda, _) = find_role_registry_address(program_id);
+    if registry_pda != *registry_account.key {
+        msg!("Role registry account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if registry_account.data_is_empty() {
+        return Ok(RoleRegistry::default());
+    }
+    if registry_account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(RoleRegistry::try_from_slice(
+        &registry_account.data.borrow(),
+    )?)
+}
+
+pub(crate) fn require_role(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo,
+    member: &AccountInfo,
+    role: Role,
+) -> ProgramResult {
+    let registry = load_role_registry(program_id, registry_account)?;
+
+    if !member.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !registry.has_role(member.key, role) {
+        msg!("Signer does not hold the {:?} role", role);
+        return Err(BiodexError::Unauthorized.into());
+    }
+
+    Ok(())
+}
+
+fn update_roles<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'a>],
+    update: impl FnOnce(&mut RoleRegistry),
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let registry_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    require_admin(program_id, config_account, admin)?;
+
+    let mut registry = load_role_registry(program_id, registry_account)?;
+    update(&mut registry);
+    save_role_registry(
+        program_id,
+        registry_account,
+        &registry,
+        payer,
+        system_program,
+    )
+}
+
+fn save_role_registry<'a>(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo<'a>,
+    registry: &RoleRegistry,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let serialized_registry = borsh::to_vec(registry)?;
+
+    if registry_account.data_is_empty() {
+        let (_, registry_bump) = find_role_registry_address(program_id);
+        msg!("Creating role registry...");
+        Processor::create_pda_account(
+            payer,
+            registry_account,
+            system_program,
+            program_id,
+            serialized_registry.len(),
+            &[b"role_registry", &[registry_bump]],
+        )?;
+    }
+
+    Processor::write_account_data(
+        registry_account,
+        &serialized_registry,
+        payer,
+        system_program,
+    )
+}
+
+// The admin starts out holding every role the minting flow needs, so a fresh deployment works
+// before anyone else has been granted one.
+pub(crate) fn initialize_role_registry<'a>(
+    program_id: &Pubkey,
+    registry_account: &AccountInfo<'a>,
+    admin: &Pubkey,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let mut registry = load_role_registry(program_id, registry_account)?;
+    for role in [Role::Curator, Role::QuizMaster, Role::AttestationOracle] {
+        let grant = RoleGrant {
+            member: *admin,
+            role,
+        };
+        if !registry.grants.contains(&grant) {
+            registry.grants.push(grant);
+        }
+    }
+
+    save_role_registry(
+        program_id,
+        registry_account,
+        &registry,
+        payer,
+        system_program,
+    )
+}
+
+pub fn grant_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    member: Pubkey,
+    role: Role,
+) -> ProgramResult {
+    msg!("Granting {:?} to {}", role, member);
+    update_roles(program_id, accounts, |registry| {
+        let grant = RoleGrant { member, role };
+        if !registry.grants.contains(&grant) {
+            registry.grants.push(grant);
+        }
+    })
+}
+
+pub fn revoke_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    member: Pubkey,
+    role: Role,
+) -> ProgramResult {
+    msg!("Revoking {:?} from {}", role, member);
+    update_roles(program_id, accounts, |registry| {
+        registry
+            .grants
+            .retain(|grant| grant.member != member || grant.role != role);
+    })
+}
@@ -0,0 +1,150 @@
+use {
+    crate::{
+        config::MAX_BPS,
+        instruction::{ProgramConfig, RarityOdds, Role, Season},
+        processor::Processor,
+        roles::require_role,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub const MAX_SEASON_PLANTS: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct CreateSeasonArgs {
+    pub season_id: u64,
+    pub name: String,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub plants: Vec<String>,
+    pub epic_cap_bonus: u64,
+    pub rare_cap_bonus: u64,
+    pub odds: Option<RarityOdds>,
+    pub odds_decay: bool,
+    pub bloom_mint: Pubkey,
+    pub bloom_supply: u64,
+}
+
+pub fn find_season_address(program_id: &Pubkey, season_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"season", &season_id.to_le_bytes()], program_id)
+}
+
+// Returns the configured season if it is running now and lists `plant_name`.
+// `season_account` is only required while the config names an active season.
+pub(crate) fn load_active_season(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    season_account: Option<&AccountInfo>,
+    plant_name: &str,
+) -> Result<Option<Season>, ProgramError> {
+    let Some(season_id) = config.active_season else {
+        return Ok(None);
+    };
+    let season_account = season_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let (season_pda, _) = find_season_address(program_id, season_id);
+    if season_pda != *season_account.key || season_account.owner != program_id {
+        msg!("Season account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let season = Season::try_from_slice(&season_account.data.borrow())?;
+    let now = Clock::get()?.unix_timestamp;
+    if !season.is_running(now) || !season.includes(plant_name) {
+        return Ok(None);
+    }
+
+    msg!("Season {} applies to {}", season.name, plant_name);
+    Ok(Some(season))
+}
+
+pub(crate) fn save_season(season_account: &AccountInfo, season: &Season) -> ProgramResult {
+    season_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&borsh::to_vec(season)?);
+    Ok(())
+}
+
+pub fn create_season(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateSeasonArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let curator = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let season_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    require_role(program_id, role_registry_account, curator, Role::Curator)?;
+
+    if args.starts_at >= args.ends_at {
+        msg!("Season must end after it starts");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if args.plants.is_empty() || args.plants.len() > MAX_SEASON_PLANTS {
+        msg!("A season lists between 1 and {} plants", MAX_SEASON_PLANTS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if let Some(odds) = &args.odds {
+        if odds.mythic_bps as u32 + odds.astral_bps as u32 > MAX_BPS {
+            msg!("Season odds add up to more than {} bps", MAX_BPS);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let (season_pda, season_bump) = find_season_address(program_id, args.season_id);
+    if season_pda != *season_account.key {
+        msg!("Season account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if season_account.lamports() > 0 {
+        msg!("Season {} already exists", args.season_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let season = borsh::to_vec(&Season {
+        season_id: args.season_id,
+        name: args.name,
+        starts_at: args.starts_at,
+        ends_at: args.ends_at,
+        plants: args.plants,
+        epic_cap_bonus: args.epic_cap_bonus,
+        rare_cap_bonus: args.rare_cap_bonus,
+        odds: args.odds,
+        odds_decay: args.odds_decay,
+        bloom_mint: args.bloom_mint,
+        bloom_supply: args.bloom_supply,
+        blooms_minted: 0,
+    })?;
+
+    msg!("Creating season {}...", args.season_id);
+    Processor::create_pda_account(
+        payer,
+        season_account,
+        system_program,
+        program_id,
+        season.len(),
+        &[b"season", &args.season_id.to_le_bytes(), &[season_bump]],
+    )?;
+
+    season_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&season);
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use {
+    crate::{
+        config::{load_config_or_default, require_not_paused},
+        error::BiodexError,
+        instruction::{
+            DiscoveryContext, DiscoveryKind, DiscoveryState, PauseScope, PlantCounter, Role,
+            SpeciesClaim,
+        },
+        processor::{Processor, MINT_ACCOUNT_COUNT},
+        roles::require_role,
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+// ApproveClaim and RejectClaim take the reviewer, role registry and claim, then the MintNFT accounts.
+const REVIEW_ACCOUNT_COUNT: usize = 3;
+
+pub fn find_species_claim_address(
+    program_id: &Pubkey,
+    plant_name: &str,
+    user: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"species_claim", plant_name.as_bytes(), user.as_ref()],
+        program_id,
+    )
+}
+
+pub fn submit_species_claim(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    context: Option<DiscoveryContext>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let plant_counter_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if context.as_ref().is_some_and(|context| !context.is_valid()) {
+        msg!("Discovery context geohash is too precise or not base32");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // A claim is a discovery, so it is paused with MintNFT. It only counts against the daily
+    // limit once the review mints its card.
+    require_not_paused(
+        &load_config_or_default(program_id, config_account)?,
+        PauseScope::Discovery,
+    )?;
+
+    let (plant_counter_pda, _) =
+        Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], program_id);
+    if plant_counter_pda != *plant_counter_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if plant_counter_account.owner == program_id {
+        let counter = PlantCounter::try_from_slice(&plant_counter_account.data.borrow())?;
+        if let DiscoveryState::Discovered {
+            kind: DiscoveryKind::NewSpecies,
+            ..
+        } = counter.discovery
+        {
+            msg!("{} is already recorded as a new species", plant_name);
+            return Err(BiodexError::SpeciesAlreadyRecorded.into());
+        }
+    }
+
+    let (claim_pda, claim_bump) = find_species_claim_address(program_id, plant_name, user.key);
+    if claim_pda != *claim_account.key {
+        msg!("Species claim account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if claim_account.lamports() > 0 {
+        msg!("A species claim for {} is already pending", plant_name);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let claim = borsh::to_vec(&SpeciesClaim {
+        user: *user.key,
+        plant_name: plant_name.to_string(),
+        context,
+        submitted_at: Clock::get()?.unix_timestamp,
+    })?;
+
+    msg!("Submitting new species claim for {}", plant_name);
+    Processor::create_pda_account(
+        payer,
+        claim_account,
+        system_program,
+        program_id,
+        claim.len(),
+        &[
+            b"species_claim",
+            plant_name.as_bytes(),
+            user.key.as_ref(),
+            &[claim_bump],
+        ],
+    )?;
+    claim_account.try_borrow_mut_data()?.copy_from_slice(&claim);
+
+    Ok(())
+}
+
+// Checks the reviewer and the pending claim. The claim is closed by `close_claim`.
+fn load_claim(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    user: &Pubkey,
+) -> Result<SpeciesClaim, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(accounts_iter)?;
+    let role_registry_account = next_account_info(accounts_iter)?;
+    let claim_account = next_account_info(accounts_iter)?;
+    let user_wallet_account = next_account_info(accounts_iter)?;
+
+    require_role(program_id, role_registry_account, reviewer, Role::Reviewer)?;
+
+    let (claim_pda, _) = find_species_claim_address(program_id, plant_name, user);
+    if claim_pda != *claim_account.key || claim_account.owner != program_id {
+        msg!("Species claim account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if user_wallet_account.key != user {
+        msg!("User wallet does not match the claimant");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(SpeciesClaim::try_from_slice(&claim_account.data.borrow())?)
+}
+
+// Refunds the claim rent to the claimant. Runs after the mint CPIs, which include the user
+// wallet but not the claim, so lamports only move once they are done.
+fn close_claim(accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter().skip(REVIEW_ACCOUNT_COUNT - 1);
+    let claim_account = next_account_info(accounts_iter)?;
+    let user_wallet_account = next_account_info(accounts_iter)?;
+
+    Processor::close_program_account(claim_account, user_wallet_account)
+}
+
+pub fn approve_claim<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    plant_name: &str,
+    user: &Pubkey,
+) -> ProgramResult {
+    let claim = load_claim(program_id, accounts, plant_name, user)?;
+    msg!("Species claim for {} approved", plant_name);
+
+    // Mints as a new species: AuroraSeed, unless another claim was approved first.
+    let mint_accounts = &accounts[REVIEW_ACCOUNT_COUNT..];
+    Processor::process_minting(
+        program_id,
+        mint_accounts
+            .get(..MINT_ACCOUNT_COUNT)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?,
+        plant_name,
+        Some(true),
+        None,
+        claim.context,
+        mint_accounts.get(MINT_ACCOUNT_COUNT..).unwrap_or_default(),
+    )?;
+
+    close_claim(accounts)
+}
+
+pub fn reject_claim<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    plant_name: &str,
+    user: &Pubkey,
+    mint_regular: bool,
+) -> ProgramResult {
+    let claim = load_claim(program_id, accounts, plant_name, user)?;
+    msg!("Species claim for {} rejected", plant_name);
+
+    if !mint_regular {
+        return close_claim(accounts);
+    }
+
+    let mint_accounts = &accounts[REVIEW_ACCOUNT_COUNT..];
+    Processor::process_minting(
+        program_id,
+        mint_accounts
+            .get(..MINT_ACCOUNT_COUNT)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?,
+        plant_name,
+        Some(false),
+        None,
+        claim.context,
+        mint_accounts.get(MINT_ACCOUNT_COUNT..).unwrap_or_default(),
+    )?;
+
+    close_claim(accounts)
+}
@@ -0,0 +1,185 @@
+use {
+    crate::{
+        collection::{add_card, load_plant_id, remove_card},
+        events::{emit, BiodexEvent},
+        instruction::{find_ownership_address, CardRarityInstruction, OwnershipRecord},
+        mint::check_token_program,
+        processor::Processor,
+        profile::{self, receive_card},
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+    spl_token_2022::instruction as token_instruction,
+};
+
+pub fn transfer_card(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plant_name: &str,
+    card_type: CardRarityInstruction,
+    quiz_id: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let sender = next_account_info(accounts_iter)?;
+    let recipient = next_account_info(accounts_iter)?;
+    let mint_account = next_account_info(accounts_iter)?;
+    let sender_token_account = next_account_info(accounts_iter)?;
+    let recipient_token_account = next_account_info(accounts_iter)?;
+    let sender_ownership_account = next_account_info(accounts_iter)?;
+    let recipient_ownership_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let rent = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let plant_counter_account = next_account_info(accounts_iter)?;
+    let sender_page_account = next_account_info(accounts_iter)?;
+    let recipient_page_account = next_account_info(accounts_iter)?;
+    let sender_profile_account = next_account_info(accounts_iter)?;
+    let recipient_profile_account = next_account_info(accounts_iter)?;
+
+    if !sender.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    check_token_program(token_program)?;
+
+    // Otherwise the card could land in any account while the record names the recipient.
+    let recipient_ata = get_associated_token_address_with_program_id(
+        recipient.key,
+        mint_account.key,
+        token_program.key,
+    );
+    if recipient_ata != *recipient_token_account.key {
+        msg!("Recipient token account is not the recipient's associated token account");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (sender_ownership_pda, _) = find_ownership_address(
+        program_id,
+        plant_name,
+        sender.key,
+        card_type.clone(),
+        quiz_id,
+    );
+    if sender_ownership_pda != *sender_ownership_account.key
+        || sender_ownership_account.owner != program_id
+    {
+        msg!("Ownership account does not match derived PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let record = OwnershipRecord::try_from_slice(&sender_ownership_account.data.borrow())?;
+    if record.owner != *sender.key || record.nft_mint != *mint_account.key {
+        msg!("Ownership record does not belong to this sender and mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (_, recipient_ownership_bump) = Processor::process_ownership_account(
+        recipient_ownership_account,
+        program_id,
+        plant_name,
+        card_type.clone(),
+        quiz_id,
+        recipient,
+    )?;
+
+    Processor::ensure_associated_token_account(
+        recipient_token_account,
+        payer,
+        recipient,
+        mint_account,
+        system_program,
+        token_program,
+        rent,
+    )?;
+
+    msg!(
+        "Transferring {:?} card for plant {} to {}",
+        record.rarity,
+        plant_name,
+        recipient.key
+    );
+    // Non-transferable Token-2022 mints reject this CPI, which keeps the record in place.
+    invoke(
+        &token_instruction::transfer_checked(
+            token_program.key,
+            sender_token_account.key,
+            mint_account.key,
+            recipient_token_account.key,
+            sender.key,
+            &[],
+            1,
+            0,
+        )?,
+        &[
+            sender_token_account.clone(),
+            mint_account.clone(),
+            recipient_token_account.clone(),
+            sender.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    if quiz_id.is_none() {
+        let plant_id = load_plant_id(program_id, plant_counter_account, plant_name)?;
+        remove_card(
+            program_id,
+            sender_page_account,
+            sender.key,
+            plant_id,
+            &card_type,
+        )?;
+        add_card(
+            program_id,
+            recipient_page_account,
+            payer,
+            system_program,
+            recipient.key,
+            plant_id,
+            &card_type,
+        )?;
+    }
+
+    Processor::create_ownership_record(
+        payer,
+        recipient_ownership_account,
+        system_program,
+        program_id,
+        &OwnershipRecord {
+            owner: *recipient.key,
+            ..record
+        },
+        recipient_ownership_bump,
+    )?;
+
+    profile::remove_card(program_id, sender_profile_account, sender.key, &card_type)?;
+    receive_card(
+        program_id,
+        recipient_profile_account,
+        payer,
+        system_program,
+        recipient.key,
+        plant_name,
+        &card_type,
+    )?;
+
+    emit(&BiodexEvent::CardTransferred {
+        from: *sender.key,
+        to: *recipient.key,
+        plant_name: plant_name.to_string(),
+        rarity: card_type,
+        mint: *mint_account.key,
+    });
+
+    // Closed last, since the sender may also be the payer of the CPIs above.
+    Processor::close_program_account(sender_ownership_account, sender)
+}
@@ -1,23 +1,67 @@
 #[cfg(test)]
 mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
     use biodex::{
-        instruction::{CardRarityInstruction, OwnershipRecord, PlantCounter, ProgramInstruction},
-        mint::CreateTokenArgs,
+        collection::{
+            collection_page_index, find_collection_page_address, find_plant_registry_address,
+        },
+        config::find_config_address,
+        error::BiodexError,
+        events::{decode_logs, BiodexEvent, EVENT_TAG, EVENT_VERSION},
+        fuse::FuseArgs,
+        handler::get_user_cards_for_plant,
+        instruction::{
+            find_ownership_address, CardRarityInstruction, CollectionPage, ConfigUpdate,
+            DiscoveryContext, DiscoveryKind, DiscoveryState, MintRateLimit, Multisig,
+            OwnershipRecord, OwnershipTree, PauseScope, PlantCounter, PlantRegistry, PlantSet,
+            ProgramConfig, ProgramInstruction, Proposal, ProposalAccount, RarityOdds,
+            ReclaimTarget, RegionCounter, Role, RoleRegistry, Season, UserProfile,
+        },
+        merkle::{
+            build_proof, compute_root, current_root, find_ownership_tree_address, ownership_leaf,
+            VerifyOwnershipArgs,
+        },
+        mint::{find_mint_authority_address, CreateTokenArgs},
+        plant_set::{find_plant_set_address, find_set_claim_address, CreatePlantSetArgs},
         process_instruction,
+        profile::{find_user_profile_address, plant_hash},
+        proposal::{find_multisig_authority_address, find_proposal_address},
+        quiz::{
+            answer_hash, find_quiz_address, find_quiz_result_address, participant_commitment,
+            CreateQuizArgs, QUIZ_CLAIM_WINDOW,
+        },
+        randomness::{find_mint_request_address, mint_commitment},
+        rate_limit::{find_rate_limit_address, RATE_LIMIT_WINDOW},
+        region::find_region_counter_address,
+        roles::find_role_registry_address,
+        season::{find_season_address, CreateSeasonArgs},
+        species::find_species_claim_address,
     };
-    use borsh::BorshSerialize;
+    use borsh::{BorshDeserialize, BorshSerialize};
     use solana_program_test::*;
     use solana_sdk::{
-        instruction::{AccountMeta, Instruction},
+        account::Account,
+        account_info::AccountInfo,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Clock,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
         system_instruction, system_program,
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     };
     use spl_associated_token_account::{
-        get_associated_token_address, instruction::create_associated_token_account,
+        get_associated_token_address, get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account,
     };
     use spl_token::id as token_program_id;
+    use spl_token_2022::extension::{
+        metadata_pointer::MetadataPointer, non_transferable::NonTransferable,
+        BaseStateWithExtensions, StateWithExtensions,
+    };
+    use spl_token_metadata_interface::state::TokenMetadata;
 
     async fn create_funded_keypair(
         banks_client: &mut BanksClient,
@@ -33,11 +77,62 @@ mod tests {
         keypair
     }
 
+    // processor! programs have no ProgramData account, so register the one a deploy creates,
+    // naming a funded keypair as the upgrade authority allowed to run InitializeConfig.
+    fn add_upgrade_authority(program_test: &mut ProgramTest, program_id: Pubkey) -> Keypair {
+        let upgrade_authority = Keypair::new();
+        program_test.add_account(
+            upgrade_authority.pubkey(),
+            Account::new(1_000_000_000, 0, &system_program::id()),
+        );
+
+        let program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(upgrade_authority.pubkey()),
+        })
+        .unwrap();
+        program_test.add_account(
+            find_program_data_address(&program_id),
+            Account {
+                lamports: 1_000_000_000,
+                data: program_data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        upgrade_authority
+    }
+
+    fn find_program_data_address(program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id()).0
+    }
+
     async fn setup_mints(
         banks_client: &mut BanksClient,
         payer: &Keypair,
         program_id: Pubkey,
         mint_authority: &Keypair,
+    ) -> (Keypair, Keypair, Keypair, Keypair, Keypair) {
+        setup_mints_with_program(
+            banks_client,
+            payer,
+            program_id,
+            mint_authority,
+            token_program_id(),
+            false,
+        )
+        .await
+    }
+
+    async fn setup_mints_with_program(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        mint_authority: &Keypair,
+        token_program: Pubkey,
+        non_transferable: bool,
     ) -> (Keypair, Keypair, Keypair, Keypair, Keypair) {
         let common_mint = Keypair::new();
         let rare_mint = Keypair::new();
@@ -58,6 +153,7 @@ mod tests {
                 nft_title: title.to_string(),
                 nft_symbol: symbol.to_string(),
                 nft_uri: "https://example.com/nft.json".to_string(),
+                non_transferable,
             };
 
             let data = ProgramInstruction::CreateMint { args }
@@ -72,7 +168,10 @@ mod tests {
                     AccountMeta::new(payer.pubkey(), true),
                     AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
                     AccountMeta::new_readonly(system_program::id(), false),
-                    AccountMeta::new_readonly(token_program_id(), false),
+                    AccountMeta::new_readonly(token_program, false),
+                    AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                    AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                    AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
                 ],
             );
 
@@ -110,6 +209,7 @@ mod tests {
                 nft_title: title.to_string(),
                 nft_symbol: symbol.to_string(),
                 nft_uri: "https://example.com/nft.json".to_string(),
+                non_transferable: false,
             };
 
             let data = ProgramInstruction::CreateMint { args }
@@ -125,6 +225,9 @@ mod tests {
                     AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
                     AccountMeta::new_readonly(system_program::id(), false),
                     AccountMeta::new_readonly(token_program_id(), false),
+                    AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                    AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                    AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
                 ],
             );
 
@@ -153,7 +256,86 @@ mod tests {
         aurora_mint: &Keypair,
         primordial_mint: &Keypair,
     ) {
-        let ata = get_associated_token_address(&user.pubkey(), &expected_mint.pubkey());
+        mint_card_with_program(
+            banks_client,
+            payer,
+            program_id,
+            user,
+            mint_authority,
+            plant_name,
+            is_new_species,
+            expected_rarity,
+            expected_mint,
+            common_mint,
+            rare_mint,
+            epic_mint,
+            aurora_mint,
+            primordial_mint,
+            token_program_id(),
+        )
+        .await
+    }
+
+    async fn mint_card_with_program(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        user: &Keypair,
+        mint_authority: &Keypair,
+        plant_name: &str,
+        is_new_species: bool,
+        expected_rarity: CardRarityInstruction,
+        expected_mint: &Keypair,
+        common_mint: &Keypair,
+        rare_mint: &Keypair,
+        epic_mint: &Keypair,
+        aurora_mint: &Keypair,
+        primordial_mint: &Keypair,
+        token_program: Pubkey,
+    ) {
+        let ix = mint_nft_ix(
+            program_id,
+            payer,
+            user,
+            mint_authority,
+            plant_name,
+            is_new_species,
+            expected_rarity,
+            expected_mint,
+            [
+                common_mint,
+                rare_mint,
+                epic_mint,
+                aurora_mint,
+                primordial_mint,
+            ],
+            token_program,
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user, mint_authority], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    // Mints are passed in MintNFT order: common, rare, epic, aurora, primordial.
+    fn mint_nft_ix(
+        program_id: Pubkey,
+        payer: &Keypair,
+        user: &Keypair,
+        mint_authority: &Keypair,
+        plant_name: &str,
+        is_new_species: bool,
+        expected_rarity: CardRarityInstruction,
+        expected_mint: &Keypair,
+        [common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint]: [&Keypair; 5],
+        token_program: Pubkey,
+    ) -> Instruction {
+        let ata = get_associated_token_address_with_program_id(
+            &user.pubkey(),
+            &expected_mint.pubkey(),
+            &token_program,
+        );
         let ownership_pda = Pubkey::find_program_address(
             &[
                 plant_name.as_bytes(),
@@ -167,13 +349,13 @@ mod tests {
         let plant_counter_pda =
             Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
 
-        let ix = Instruction::new_with_bytes(
+        Instruction::new_with_bytes(
             program_id,
             &ProgramInstruction::MintNFT {
                 card_type: CardRarityInstruction::GenesisFragment,
                 plant_name: plant_name.to_string(),
                 is_new_species: Some(is_new_species),
-                quiz_winner: Some(false),
+                context: None,
             }
             .try_to_vec()
             .unwrap(),
@@ -189,272 +371,5528 @@ mod tests {
                 AccountMeta::new(payer.pubkey(), true),
                 AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
                 AccountMeta::new_readonly(system_program::id(), false),
-                AccountMeta::new_readonly(token_program_id(), false),
+                AccountMeta::new_readonly(token_program, false),
                 AccountMeta::new(ownership_pda, false),
                 AccountMeta::new(plant_counter_pda, false),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_user_profile_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new(find_plant_registry_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_collection_page_address(&program_id, &user.pubkey(), 0).0,
+                    false,
+                ),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_rate_limit_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
                 AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             ],
+        )
+    }
+
+    async fn submit_species_claim(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        user: &Keypair,
+        plant_name: &str,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::SubmitSpeciesClaim {
+                plant_name: plant_name.to_string(),
+                context: None,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(user.pubkey(), true),
+                AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[b"plant_counter", plant_name.as_bytes()],
+                        &program_id,
+                    )
+                    .0,
+                    false,
+                ),
+                AccountMeta::new(
+                    find_species_claim_address(&program_id, plant_name, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+            ],
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    // ApproveClaim / RejectClaim: reviewer, role registry and claim in front of the MintNFT accounts.
+    fn review_claim_ix(
+        program_id: Pubkey,
+        reviewer: &Keypair,
+        plant_name: &str,
+        user: &Pubkey,
+        review: ProgramInstruction,
+        mint_ix: Instruction,
+    ) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(reviewer.pubkey(), true),
+            AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+            AccountMeta::new(
+                find_species_claim_address(&program_id, plant_name, user).0,
+                false,
+            ),
+        ];
+        // The claimant does not sign the review.
+        accounts.extend(mint_ix.accounts.into_iter().map(|mut account| {
+            if account.pubkey == *user {
+                account.is_signer = false;
+            }
+            account
+        }));
+
+        Instruction::new_with_bytes(program_id, &review.try_to_vec().unwrap(), accounts)
+    }
+
+    async fn warp_clock(context: &mut ProgramTestContext, unix_timestamp: i64) {
+        // Moving to a new slot also rotates the blockhash, so retried transactions are not deduped.
+        let slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(slot + 2).unwrap();
+
+        let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        context.set_sysvar(&clock);
+    }
+
+    async fn create_quiz(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        authority: &Keypair,
+        quiz_id: u64,
+        plant_name: &str,
+        answers: &[u8],
+        salt: &[u8; 32],
+        closes_at: i64,
+        max_winners: u64,
+    ) {
+        try_create_quiz(
+            banks_client,
+            payer,
+            program_id,
+            authority,
+            quiz_id,
+            plant_name,
+            answers,
+            salt,
+            closes_at,
+            max_winners,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn try_create_quiz(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        authority: &Keypair,
+        quiz_id: u64,
+        plant_name: &str,
+        answers: &[u8],
+        salt: &[u8; 32],
+        closes_at: i64,
+        max_winners: u64,
+    ) -> Result<(), BanksClientError> {
+        let quiz_pda = find_quiz_address(&program_id, quiz_id).0;
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::CreateQuiz {
+                args: CreateQuizArgs {
+                    quiz_id,
+                    plant_name: plant_name.to_string(),
+                    answer_hash: answer_hash(answers, salt),
+                    opens_at: 0,
+                    closes_at,
+                    max_winners,
+                },
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new(quiz_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+            ],
         );
 
         let blockhash = banks_client.get_latest_blockhash().await.unwrap();
         let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
-        tx.sign(&[&payer, user, mint_authority], blockhash);
-        banks_client.process_transaction(tx).await.unwrap();
+        tx.sign(&[payer, authority], blockhash);
+        banks_client.process_transaction(tx).await
     }
 
-    async fn mint_quiz_card(
+    async fn commit_quiz_answers(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        user: &Keypair,
+        quiz_id: u64,
+        answers: &[u8],
+        salt: &[u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let quiz_pda = find_quiz_address(&program_id, quiz_id).0;
+        let quiz_result_pda = find_quiz_result_address(&program_id, quiz_id, &user.pubkey()).0;
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::CommitQuizAnswers {
+                quiz_id,
+                commitment: participant_commitment(&user.pubkey(), answers, salt),
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(user.pubkey(), true),
+                AccountMeta::new(quiz_pda, false),
+                AccountMeta::new(quiz_result_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn settle_quiz(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        authority: &Keypair,
+        quiz_id: u64,
+        answers: &[u8],
+        salt: &[u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let quiz_pda = find_quiz_address(&program_id, quiz_id).0;
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::SettleQuiz {
+                quiz_id,
+                answers: answers.to_vec(),
+                salt: *salt,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new(quiz_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, authority], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn submit_quiz_result(
         banks_client: &mut BanksClient,
         payer: &Keypair,
         program_id: Pubkey,
         user: &Keypair,
-        mint_authority: &Keypair,
         plant_name: &str,
-        quiz: bool,
+        quiz_id: u64,
+        answers: &[u8],
+        salt: &[u8; 32],
         expected_rarity: CardRarityInstruction,
         expected_mint: &Keypair,
         codex_mint: &Keypair,
         ascendent_mint: &Keypair,
-    ) {
+    ) -> Result<(), BanksClientError> {
         let ata = get_associated_token_address(&user.pubkey(), &expected_mint.pubkey());
-        let ownership_pda = Pubkey::find_program_address(
-            &[
-                plant_name.as_bytes(),
-                user.pubkey().as_ref(),
-                &[expected_rarity.clone() as u8],
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            expected_rarity,
+            Some(quiz_id),
+        )
+        .0;
+
+        let quiz_pda = find_quiz_address(&program_id, quiz_id).0;
+        let quiz_result_pda = find_quiz_result_address(&program_id, quiz_id, &user.pubkey()).0;
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::SubmitQuizResult {
+                quiz_id,
+                answers: answers.to_vec(),
+                salt: *salt,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new(quiz_pda, false),
+                AccountMeta::new(quiz_result_pda, false),
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new(codex_mint.pubkey(), false),
+                AccountMeta::new(ascendent_mint.pubkey(), false),
+                AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
+                AccountMeta::new(ata, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(token_program_id(), false),
+                AccountMeta::new(ownership_pda, false),
+                AccountMeta::new(plant_counter_pda, false),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_user_profile_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new(
+                    find_rate_limit_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new(find_plant_registry_address(&program_id).0, false),
+                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn burn_card(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        holder: &Keypair,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = burn_card_ix(program_id, holder, plant_name, card_type, quiz_id, mint);
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, holder], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    fn burn_card_ix(
+        program_id: Pubkey,
+        holder: &Keypair,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        quiz_id: Option<u64>,
+        mint: &Pubkey,
+    ) -> Instruction {
+        let token_account = get_associated_token_address(&holder.pubkey(), mint);
+        let ownership_pda = find_ownership_address(
             &program_id,
+            plant_name,
+            &holder.pubkey(),
+            card_type.clone(),
+            quiz_id,
         )
         .0;
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+
+        Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::BurnCard {
+                plant_name: plant_name.to_string(),
+                card_type,
+                quiz_id,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new(holder.pubkey(), true),
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(token_account, false),
+                AccountMeta::new(ownership_pda, false),
+                AccountMeta::new(plant_counter_pda, false),
+                AccountMeta::new_readonly(token_program_id(), false),
+                AccountMeta::new(
+                    find_collection_page_address(&program_id, &holder.pubkey(), 0).0,
+                    false,
+                ),
+                AccountMeta::new(
+                    find_user_profile_address(&program_id, &holder.pubkey()).0,
+                    false,
+                ),
+            ],
+        )
+    }
+
+    async fn initialize_config(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+    ) {
+        try_initialize_config(banks_client, payer, program_id, admin)
+            .await
+            .unwrap();
+    }
+
+    async fn try_initialize_config(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::InitializeConfig.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new_readonly(admin.pubkey(), true),
+                AccountMeta::new(find_config_address(&program_id).0, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(find_program_data_address(&program_id), false),
+                AccountMeta::new(find_role_registry_address(&program_id).0, false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, admin], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn update_config(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+        update: ConfigUpdate,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::UpdateConfig { update }
+                .try_to_vec()
+                .unwrap(),
+            vec![
+                AccountMeta::new_readonly(admin.pubkey(), true),
+                AccountMeta::new(find_config_address(&program_id).0, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, admin], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn update_role(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+        update: ProgramInstruction,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &update.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new_readonly(admin.pubkey(), true),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, admin], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn grant_role(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+        member: &Pubkey,
+        role: Role,
+    ) {
+        update_role(
+            banks_client,
+            payer,
+            program_id,
+            admin,
+            ProgramInstruction::GrantRole {
+                member: *member,
+                role,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // Hands a separate mint authority the roles InitializeConfig only gives the admin.
+    async fn grant_minting_roles(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        admin: &Keypair,
+        member: &Pubkey,
+    ) {
+        for role in [Role::Curator, Role::QuizMaster, Role::AttestationOracle] {
+            grant_role(banks_client, payer, program_id, admin, member, role).await;
+        }
+    }
+
+    fn reclaim_rent_ix(
+        program_id: Pubkey,
+        fee_manager: &Pubkey,
+        target: ReclaimTarget,
+        target_account: &Pubkey,
+        receiver: &Pubkey,
+    ) -> Instruction {
+        Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::ReclaimRent { target }
+                .try_to_vec()
+                .unwrap(),
+            vec![
+                AccountMeta::new_readonly(*fee_manager, true),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(*target_account, false),
+                AccountMeta::new(*receiver, false),
+            ],
+        )
+    }
+
+    // Reclaims a discovery card record, passing the owner's token account, plant counter
+    // and first collection page.
+    fn reclaim_record_ix(
+        program_id: Pubkey,
+        fee_manager: &Pubkey,
+        owner: &Pubkey,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        mint: &Pubkey,
+        receiver: &Pubkey,
+    ) -> Instruction {
+        let ownership_pda =
+            find_ownership_address(&program_id, plant_name, owner, card_type.clone(), None).0;
+        let mut ix = reclaim_rent_ix(
+            program_id,
+            fee_manager,
+            ReclaimTarget::OwnershipRecord {
+                owner: *owner,
+                plant_name: plant_name.to_string(),
+                card_type,
+                quiz_id: None,
+            },
+            &ownership_pda,
+            receiver,
+        );
+        ix.accounts.extend([
+            AccountMeta::new_readonly(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(
+                Pubkey::find_program_address(
+                    &[b"plant_counter", plant_name.as_bytes()],
+                    &program_id,
+                )
+                .0,
+                false,
+            ),
+            AccountMeta::new(find_collection_page_address(&program_id, owner, 0).0, false),
+            AccountMeta::new(find_user_profile_address(&program_id, owner).0, false),
+        ]);
+        ix
+    }
+
+    async fn transfer_card(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        sender: &Keypair,
+        recipient: &Pubkey,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        transfer_card_with_program(
+            banks_client,
+            payer,
+            program_id,
+            sender,
+            recipient,
+            plant_name,
+            card_type,
+            mint,
+            token_program_id(),
+        )
+        .await
+    }
+
+    async fn transfer_card_with_program(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        sender: &Keypair,
+        recipient: &Pubkey,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        mint: &Pubkey,
+        token_program: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = transfer_card_ix(
+            program_id,
+            &payer.pubkey(),
+            &sender.pubkey(),
+            recipient,
+            plant_name,
+            card_type,
+            mint,
+            token_program,
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, sender], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    fn transfer_card_ix(
+        program_id: Pubkey,
+        payer: &Pubkey,
+        sender: &Pubkey,
+        recipient: &Pubkey,
+        plant_name: &str,
+        card_type: CardRarityInstruction,
+        mint: &Pubkey,
+        token_program: Pubkey,
+    ) -> Instruction {
+        let sender_ownership_pda =
+            find_ownership_address(&program_id, plant_name, sender, card_type.clone(), None).0;
+        let recipient_ownership_pda =
+            find_ownership_address(&program_id, plant_name, recipient, card_type.clone(), None).0;
+
+        Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::TransferCard {
+                plant_name: plant_name.to_string(),
+                card_type,
+                quiz_id: None,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new(*sender, true),
+                AccountMeta::new_readonly(*recipient, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(
+                    get_associated_token_address_with_program_id(sender, mint, &token_program),
+                    false,
+                ),
+                AccountMeta::new(
+                    get_associated_token_address_with_program_id(recipient, mint, &token_program),
+                    false,
+                ),
+                AccountMeta::new(sender_ownership_pda, false),
+                AccountMeta::new(recipient_ownership_pda, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[b"plant_counter", plant_name.as_bytes()],
+                        &program_id,
+                    )
+                    .0,
+                    false,
+                ),
+                AccountMeta::new(
+                    find_collection_page_address(&program_id, sender, 0).0,
+                    false,
+                ),
+                AccountMeta::new(
+                    find_collection_page_address(&program_id, recipient, 0).0,
+                    false,
+                ),
+                AccountMeta::new(find_user_profile_address(&program_id, sender).0, false),
+                AccountMeta::new(find_user_profile_address(&program_id, recipient).0, false),
+                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            ],
+        )
+    }
+
+    async fn create_plant_set(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        curator: &Keypair,
+        args: CreatePlantSetArgs,
+    ) -> Result<(), BanksClientError> {
+        let set_pda = find_plant_set_address(&program_id, args.set_id).0;
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::CreatePlantSet { args }
+                .try_to_vec()
+                .unwrap(),
+            vec![
+                AccountMeta::new_readonly(curator.pubkey(), true),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(set_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, curator], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    // `cards` lists the (plant name, rarity) the user presents for each set plant, in set order.
+    async fn claim_set_reward(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        user: &Keypair,
+        set_id: u64,
+        reward_mint: &Pubkey,
+        cards: &[(&str, CardRarityInstruction)],
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = vec![
+            AccountMeta::new(user.pubkey(), true),
+            AccountMeta::new(find_plant_set_address(&program_id, set_id).0, false),
+            AccountMeta::new(
+                find_set_claim_address(&program_id, set_id, &user.pubkey()).0,
+                false,
+            ),
+            AccountMeta::new(*reward_mint, false),
+            AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
+            AccountMeta::new(
+                get_associated_token_address(&user.pubkey(), reward_mint),
+                false,
+            ),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+            AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+        ];
+        for (plant_name, rarity) in cards {
+            accounts.push(AccountMeta::new_readonly(
+                Pubkey::find_program_address(
+                    &[b"plant_counter", plant_name.as_bytes()],
+                    &program_id,
+                )
+                .0,
+                false,
+            ));
+            accounts.push(AccountMeta::new_readonly(
+                find_ownership_address(
+                    &program_id,
+                    plant_name,
+                    &user.pubkey(),
+                    rarity.clone(),
+                    None,
+                )
+                .0,
+                false,
+            ));
+        }
+        accounts.push(AccountMeta::new_readonly(
+            spl_associated_token_account::id(),
+            false,
+        ));
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::ClaimSetReward { set_id }
+                .try_to_vec()
+                .unwrap(),
+            accounts,
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    async fn fuse_cards(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        program_id: Pubkey,
+        user: &Keypair,
+        args: FuseArgs,
+        (input_mint, output_mint): (&Pubkey, &Pubkey),
+        output: CardRarityInstruction,
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = vec![
+            AccountMeta::new(user.pubkey(), true),
+            AccountMeta::new(*input_mint, false),
+            AccountMeta::new(
+                get_associated_token_address(&user.pubkey(), input_mint),
+                false,
+            ),
+            AccountMeta::new(*output_mint, false),
+            AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
+            AccountMeta::new(
+                get_associated_token_address(&user.pubkey(), output_mint),
+                false,
+            ),
+            AccountMeta::new(
+                find_ownership_address(
+                    &program_id,
+                    &args.output_plant,
+                    &user.pubkey(),
+                    output,
+                    None,
+                )
+                .0,
+                false,
+            ),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(token_program_id(), false),
+            AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+            AccountMeta::new(
+                find_user_profile_address(&program_id, &user.pubkey()).0,
+                false,
+            ),
+        ];
+        for plant_name in &args.input_plants {
+            accounts.push(AccountMeta::new(
+                Pubkey::find_program_address(
+                    &[b"plant_counter", plant_name.as_bytes()],
+                    &program_id,
+                )
+                .0,
+                false,
+            ));
+            accounts.push(AccountMeta::new(
+                find_ownership_address(
+                    &program_id,
+                    plant_name,
+                    &user.pubkey(),
+                    args.input.clone(),
+                    None,
+                )
+                .0,
+                false,
+            ));
+            accounts.push(AccountMeta::new(
+                find_collection_page_address(&program_id, &user.pubkey(), 0).0,
+                false,
+            ));
+        }
+        accounts.push(AccountMeta::new_readonly(
+            spl_associated_token_account::id(),
+            false,
+        ));
+
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::Fuse { args }.try_to_vec().unwrap(),
+            accounts,
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[payer, user], blockhash);
+        banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn test_primordial_relic_first_known_plant() {
+        println!("\n🧪 TEST: PrimordialRelic - First mint of known plant");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            "Rose",
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        println!("✅ PrimordialRelic minted for first known plant mint");
+    }
+
+    #[tokio::test]
+    async fn test_aurora_seed_new_species() {
+        println!("\n🧪 TEST: AuroraSeed - First mint of NEW species");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            "NewSpecies",
+            true,
+            CardRarityInstruction::AuroraSeed,
+            &aurora_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        println!("✅ AuroraSeed minted for new species discovery");
+    }
+
+    #[tokio::test]
+    async fn test_mythic_crest_regular_distribution() {
+        println!("\n🧪 TEST: MythicCrest - Regular distribution (2nd mint)");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        // First user gets PrimordialRelic
+        let user1 = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user1,
+            &mint_authority,
+            "Sunflower",
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+        println!("  → User 1 got PrimordialRelic");
+
+        // Second user should get MythicCrest
+        let user2 = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user2,
+            &mint_authority,
+            "Sunflower",
+            false,
+            CardRarityInstruction::MythicCrest,
+            &epic_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        println!("✅ MythicCrest minted in regular distribution");
+    }
+
+    #[tokio::test]
+    async fn test_mastery_card() {
+        println!("\nTesting Ascendental Seed");
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+
+        let plant_name = "Orange";
+        let quiz_salt = [7u8; 32];
+        let user_salt = [9u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        create_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            1,
+            plant_name,
+            b"ACDB",
+            &quiz_salt,
+            now + 60,
+            3,
+        )
+        .await;
+
+        commit_quiz_answers(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            1,
+            b"ACDB",
+            &user_salt,
+        )
+        .await
+        .unwrap();
+
+        warp_clock(&mut context, now + 61).await;
+        settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            1,
+            b"ACDB",
+            &quiz_salt,
+        )
+        .await
+        .unwrap();
+
+        println!("\nMinting AscendantSeal\n");
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            1,
+            b"ACDB",
+            &user_salt,
+            CardRarityInstruction::AscendantSeal,
+            &ascendent_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+
+        let profile_account = context
+            .banks_client
+            .get_account(find_user_profile_address(&program_id, &user.pubkey()).0)
+            .await
+            .unwrap()
+            .unwrap();
+        let profile = UserProfile::try_from_slice(&profile_account.data).unwrap();
+        assert_eq!(profile.quiz_wins, 1);
+        assert_eq!(profile.cards_of(&CardRarityInstruction::AscendantSeal), 1);
+
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let counter_account = context
+            .banks_client
+            .get_account(plant_counter_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let counter = PlantCounter::try_from_slice(&counter_account.data).unwrap();
+        assert_eq!(counter.mastery_count, 1);
+        assert_eq!(counter.codex_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_codex_card() {
+        println!("\nTesting Codex of Insight");
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+
+        let plant_name = "Orange";
+        let quiz_salt = [7u8; 32];
+        let user_salt = [9u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        create_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            1,
+            plant_name,
+            b"ACDB",
+            &quiz_salt,
+            now + 60,
+            3,
+        )
+        .await;
+
+        commit_quiz_answers(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            1,
+            b"ABCD",
+            &user_salt,
+        )
+        .await
+        .unwrap();
+
+        warp_clock(&mut context, now + 61).await;
+        settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            1,
+            b"ACDB",
+            &quiz_salt,
+        )
+        .await
+        .unwrap();
+
+        println!("\nMinting CodexOfInsight\n");
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            1,
+            b"ABCD",
+            &user_salt,
+            CardRarityInstruction::CodexOfInsight,
+            &codex_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let counter_account = context
+            .banks_client
+            .get_account(plant_counter_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let counter = PlantCounter::try_from_slice(&counter_account.data).unwrap();
+        assert_eq!(counter.codex_count, 1);
+        assert_eq!(counter.mastery_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_quiz_winner_cap() {
+        println!("\n🧪 TEST: Quiz winners are capped by max_winners");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let plant_name = "Neem";
+        let quiz_salt = [3u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        create_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            7,
+            plant_name,
+            b"BBAC",
+            &quiz_salt,
+            now + 60,
+            1,
+        )
+        .await;
+
+        let winner = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        let late = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        for (user, salt) in [(&winner, [1u8; 32]), (&late, [2u8; 32])] {
+            commit_quiz_answers(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                user,
+                7,
+                b"BBAC",
+                &salt,
+            )
+            .await
+            .unwrap();
+        }
+
+        warp_clock(&mut context, now + 61).await;
+        settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            7,
+            b"BBAC",
+            &quiz_salt,
+        )
+        .await
+        .unwrap();
+
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &winner,
+            plant_name,
+            7,
+            b"BBAC",
+            &[1u8; 32],
+            CardRarityInstruction::AscendantSeal,
+            &ascendent_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+        println!("  → First correct claim got AscendantSeal");
+
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &late,
+            plant_name,
+            7,
+            b"BBAC",
+            &[2u8; 32],
+            CardRarityInstruction::CodexOfInsight,
+            &codex_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+
+        println!("✅ Correct answer after the cap fell back to CodexOfInsight");
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_settled_quiz() {
+        println!("\n🧪 TEST: A settled quiz is reclaimable only after its claim window");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        grant_role(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            &mint_authority.pubkey(),
+            Role::FeeManager,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let plant_name = "Tulsi";
+        let quiz_salt = [4u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        create_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            9,
+            plant_name,
+            b"ACDB",
+            &quiz_salt,
+            now + 60,
+            1,
+        )
+        .await;
+
+        let early = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        let late = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        for (user, salt) in [(&early, [1u8; 32]), (&late, [2u8; 32])] {
+            commit_quiz_answers(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                user,
+                9,
+                b"ACDB",
+                &salt,
+            )
+            .await
+            .unwrap();
+        }
+
+        warp_clock(&mut context, now + 61).await;
+        settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            9,
+            b"ACDB",
+            &quiz_salt,
+        )
+        .await
+        .unwrap();
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &early,
+            plant_name,
+            9,
+            b"ACDB",
+            &[1u8; 32],
+            CardRarityInstruction::AscendantSeal,
+            &ascendent_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+
+        let quiz_pda = find_quiz_address(&program_id, 9).0;
+        let receiver = Pubkey::new_unique();
+        let reclaim_quiz = || {
+            reclaim_rent_ix(
+                program_id,
+                &mint_authority.pubkey(),
+                ReclaimTarget::Quiz { quiz_id: 9 },
+                &quiz_pda,
+                &receiver,
+            )
+        };
+
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[reclaim_quiz()], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "Reclaim before the claim deadline should fail while a participant is unclaimed"
+        );
+        println!("  → Quiz with an unclaimed participant was kept");
+
+        warp_clock(&mut context, now + 62 + QUIZ_CLAIM_WINDOW).await;
+        let err = submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &late,
+            plant_name,
+            9,
+            b"ACDB",
+            &[2u8; 32],
+            CardRarityInstruction::CodexOfInsight,
+            &codex_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(BiodexError::QuizClaimsClosed as u32)
+            )
+        );
+
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[reclaim_quiz()], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        assert!(context
+            .banks_client
+            .get_account(quiz_pda)
+            .await
+            .unwrap()
+            .is_none());
+
+        println!("✅ Quiz was reclaimed once its claim window closed");
+    }
+
+    #[tokio::test]
+    async fn test_quiz_commit_reveal_rules() {
+        println!("\n🧪 TEST: Quiz commitments are binding and time-boxed");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let plant_name = "Tulsi";
+        let quiz_salt = [5u8; 32];
+        let user_salt = [6u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        create_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            3,
+            plant_name,
+            b"DDCA",
+            &quiz_salt,
+            now + 60,
+            5,
+        )
+        .await;
+
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        commit_quiz_answers(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            3,
+            b"AAAA",
+            &user_salt,
+        )
+        .await
+        .unwrap();
+
+        let result = commit_quiz_answers(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            3,
+            b"DDCA",
+            &user_salt,
+        )
+        .await;
+        assert!(result.is_err(), "Second commitment should fail");
+
+        let result = settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            3,
+            b"DDCA",
+            &quiz_salt,
+        )
+        .await;
+        assert!(result.is_err(), "Settling an open quiz should fail");
+
+        warp_clock(&mut context, now + 61).await;
+
+        let latecomer =
+            create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        let result = commit_quiz_answers(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &latecomer,
+            3,
+            b"DDCA",
+            &user_salt,
+        )
+        .await;
+        assert!(result.is_err(), "Commitments after close should fail");
+
+        let result = settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            3,
+            b"ABCD",
+            &quiz_salt,
+        )
+        .await;
+        assert!(result.is_err(), "Settling with the wrong key should fail");
+
+        settle_quiz(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            3,
+            b"DDCA",
+            &quiz_salt,
+        )
+        .await
+        .unwrap();
+
+        let result = submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            3,
+            b"DDCA",
+            &user_salt,
+            CardRarityInstruction::AscendantSeal,
+            &ascendent_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await;
+        assert!(result.is_err(), "Revealing different answers should fail");
+
+        submit_quiz_result(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            3,
+            b"AAAA",
+            &user_salt,
+            CardRarityInstruction::CodexOfInsight,
+            &codex_mint,
+            &codex_mint,
+            &ascendent_mint,
+        )
+        .await
+        .unwrap();
+
+        println!("✅ Only the committed answers can be revealed");
+    }
+
+    #[tokio::test]
+    async fn test_multiple_quiz_cards_same_plant() {
+        println!("\n🧪 TEST: One CodexOfInsight per quiz for the same plant");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+
+        let plant_name = "Rhododendron";
+        let quiz_salt = [4u8; 32];
+        let user_salt = [8u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        for quiz_id in [11, 12] {
+            create_quiz(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                quiz_id,
+                plant_name,
+                b"CABD",
+                &quiz_salt,
+                now + 60,
+                3,
+            )
+            .await;
+
+            commit_quiz_answers(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &user,
+                quiz_id,
+                b"AAAA",
+                &user_salt,
+            )
+            .await
+            .unwrap();
+        }
+
+        warp_clock(&mut context, now + 61).await;
+
+        for quiz_id in [11, 12] {
+            settle_quiz(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                quiz_id,
+                b"CABD",
+                &quiz_salt,
+            )
+            .await
+            .unwrap();
+
+            submit_quiz_result(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &user,
+                plant_name,
+                quiz_id,
+                b"AAAA",
+                &user_salt,
+                CardRarityInstruction::CodexOfInsight,
+                &codex_mint,
+                &codex_mint,
+                &ascendent_mint,
+            )
+            .await
+            .unwrap();
+
+            let ownership_pda = find_ownership_address(
+                &program_id,
+                plant_name,
+                &user.pubkey(),
+                CardRarityInstruction::CodexOfInsight,
+                Some(quiz_id),
+            )
+            .0;
+            let account = context
+                .banks_client
+                .get_account(ownership_pda)
+                .await
+                .unwrap()
+                .unwrap();
+            let record = OwnershipRecord::try_from_slice(&account.data).unwrap();
+            assert_eq!(record.quiz_id, Some(quiz_id));
+        }
+
+        println!("✅ Same user holds a CodexOfInsight from each quiz");
+    }
+
+    #[test]
+    fn test_handler_lists_discovery_and_quiz_cards() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let plant_name = "Orange";
+
+        let cards = [
+            (CardRarityInstruction::PrimordialRelic, None),
+            (CardRarityInstruction::CodexOfInsight, Some(1)),
+            (CardRarityInstruction::AscendantSeal, Some(2)),
+        ];
+
+        let keys: Vec<Pubkey> = cards
+            .iter()
+            .map(|(card_type, quiz_id)| {
+                find_ownership_address(&program_id, plant_name, &user, card_type.clone(), *quiz_id)
+                    .0
+            })
+            .collect();
+        let mut data: Vec<Vec<u8>> = cards
+            .iter()
+            .map(|(card_type, quiz_id)| {
+                OwnershipRecord::new(
+                    user,
+                    plant_name.to_string(),
+                    card_type.clone(),
+                    Pubkey::new_unique(),
+                    *quiz_id,
+                )
+                .try_to_vec()
+                .unwrap()
+            })
+            .collect();
+        let mut lamports = vec![1_000_000u64; cards.len()];
+
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(data.iter_mut())
+            .zip(lamports.iter_mut())
+            .map(|((key, data), lamports)| {
+                AccountInfo::new(key, false, false, lamports, data, &program_id, false, 0)
+            })
+            .collect();
+
+        let only_discovery =
+            get_user_cards_for_plant(&program_id, &user, plant_name, &[], &accounts).unwrap();
+        assert_eq!(only_discovery.len(), 1);
+
+        let everything =
+            get_user_cards_for_plant(&program_id, &user, plant_name, &[1, 2], &accounts).unwrap();
+        assert_eq!(everything.len(), 3);
+        assert_eq!(everything[1].quiz_id, Some(1));
+        assert_eq!(everything[2].quiz_id, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_burn_card_closes_ownership_record() {
+        println!("\n🧪 TEST: BurnCard burns the token and closes the ownership record");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plant_name = "Jasmine";
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        let record_rent = banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        let balance_before = banks_client.get_balance(user.pubkey()).await.unwrap();
+
+        burn_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            None,
+            &primordial_mint.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        assert!(banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            banks_client.get_balance(user.pubkey()).await.unwrap(),
+            balance_before + record_rent
+        );
+
+        let token_account = banks_client
+            .get_account(get_associated_token_address(
+                &user.pubkey(),
+                &primordial_mint.pubkey(),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            spl_token::state::Account::unpack(&token_account.data)
+                .unwrap()
+                .amount,
+            0
+        );
+
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let counter_account = banks_client
+            .get_account(plant_counter_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let counter = PlantCounter::try_from_slice(&counter_account.data).unwrap();
+        assert_eq!(counter.burned_count, 1);
+        assert_eq!(counter.relic_count, 1);
+
+        println!("✅ Card burned and rent refunded to the holder");
+    }
+
+    #[tokio::test]
+    async fn test_burn_card_requires_record_owner() {
+        println!("\n🧪 TEST: Only the recorded owner can burn a card");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let stranger = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plant_name = "Lotus";
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        let result = burn_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &stranger,
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            None,
+            &primordial_mint.pubkey(),
+        )
+        .await;
+        assert!(result.is_err(), "Burning without a record should fail");
+
+        println!("✅ Stranger could not burn the card");
+    }
+
+    #[tokio::test]
+    async fn test_initialize_config_requires_upgrade_authority() {
+        println!("\n🧪 TEST: Only the upgrade authority can initialize the config");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let upgrade_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        let stranger = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let result = try_initialize_config(&mut banks_client, &payer, program_id, &stranger).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(BiodexError::Unauthorized as u32)
+            )
+        );
+
+        initialize_config(&mut banks_client, &payer, program_id, &upgrade_authority).await;
+        let config_account = banks_client
+            .get_account(find_config_address(&program_id).0)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = ProgramConfig::try_from_slice(&config_account.data).unwrap();
+        assert_eq!(config.admin, upgrade_authority.pubkey());
+
+        println!("✅ Stranger rejected, upgrade authority became admin");
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_rent_fee_manager_only() {
+        println!("\n🧪 TEST: ReclaimRent closes stale accounts for the fee manager only");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plant_name = "Bamboo";
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        let record_rent = banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        let receiver = Pubkey::new_unique();
+        let reclaim_record = |fee_manager: &Pubkey| {
+            reclaim_record_ix(
+                program_id,
+                fee_manager,
+                &user.pubkey(),
+                plant_name,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint.pubkey(),
+                &receiver,
+            )
+        };
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx =
+            Transaction::new_with_payer(&[reclaim_record(&user.pubkey())], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        assert!(
+            banks_client.process_transaction(tx).await.is_err(),
+            "Reclaim without the FeeManager role should fail"
+        );
+
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &admin.pubkey(),
+            Role::FeeManager,
+        )
+        .await;
+
+        // Live accounts are refused: the plant counter matches no reclaimable kind, and the
+        // record still backs a held card.
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        for ix in [
+            reclaim_rent_ix(
+                program_id,
+                &admin.pubkey(),
+                ReclaimTarget::Quiz { quiz_id: 0 },
+                &plant_counter_pda,
+                &receiver,
+            ),
+            reclaim_record(&admin.pubkey()),
+        ] {
+            let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+            let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+            tx.sign(&[&payer, &admin], blockhash);
+            assert!(banks_client.process_transaction(tx).await.is_err());
+        }
+
+        let friend = Keypair::new();
+        let user_ata = get_associated_token_address(&user.pubkey(), &primordial_mint.pubkey());
+        let friend_ata = get_associated_token_address(&friend.pubkey(), &primordial_mint.pubkey());
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[
+                create_associated_token_account(
+                    &payer.pubkey(),
+                    &friend.pubkey(),
+                    &primordial_mint.pubkey(),
+                    &token_program_id(),
+                ),
+                spl_token::instruction::transfer(
+                    &token_program_id(),
+                    &user_ata,
+                    &friend_ata,
+                    &user.pubkey(),
+                    &[],
+                    1,
+                )
+                .unwrap(),
+                reclaim_record(&admin.pubkey()),
+            ],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &user, &admin], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            banks_client.get_balance(receiver).await.unwrap(),
+            record_rent
+        );
+
+        println!("✅ Fee manager reclaimed only the stale record");
+    }
+
+    #[tokio::test]
+    async fn test_reclaimed_record_cannot_be_revived() {
+        println!("\n🧪 TEST: A closed ownership record stays closed after a lamport top-up");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plant_name = "Fern";
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &admin.pubkey(),
+            Role::FeeManager,
+        )
+        .await;
+        // The card has to leave the wallet before its record counts as stale.
+        let friend = Keypair::new();
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[
+                create_associated_token_account(
+                    &payer.pubkey(),
+                    &friend.pubkey(),
+                    &primordial_mint.pubkey(),
+                    &token_program_id(),
+                ),
+                spl_token::instruction::transfer(
+                    &token_program_id(),
+                    &get_associated_token_address(&user.pubkey(), &primordial_mint.pubkey()),
+                    &get_associated_token_address(&friend.pubkey(), &primordial_mint.pubkey()),
+                    &user.pubkey(),
+                    &[],
+                    1,
+                )
+                .unwrap(),
+                reclaim_record_ix(
+                    program_id,
+                    &admin.pubkey(),
+                    &user.pubkey(),
+                    plant_name,
+                    CardRarityInstruction::PrimordialRelic,
+                    &primordial_mint.pubkey(),
+                    &admin.pubkey(),
+                ),
+                system_instruction::transfer(&payer.pubkey(), &ownership_pda, 10_000_000),
+            ],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &user, &admin], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let revived = banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(revived.owner, system_program::id());
+        assert!(revived.data.is_empty());
+
+        let result = burn_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            None,
+            &primordial_mint.pubkey(),
+        )
+        .await;
+        assert!(result.is_err(), "Revived record must not be usable");
+
+        println!("✅ Topped-up account is a plain system account");
+    }
+
+    #[tokio::test]
+    async fn test_close_stale_ownership_record() {
+        println!("\n🧪 TEST: CloseOwnership only closes records for cards no longer held");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let friend = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plant_name = "Cactus";
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        let user_ata = get_associated_token_address(&user.pubkey(), &primordial_mint.pubkey());
+        let friend_ata = get_associated_token_address(&friend.pubkey(), &primordial_mint.pubkey());
+        let page_pda = find_collection_page_address(&program_id, &user.pubkey(), 0).0;
+
+        let close_ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::CloseOwnership {
+                plant_name: plant_name.to_string(),
+                card_type: CardRarityInstruction::PrimordialRelic,
+                quiz_id: None,
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new(ownership_pda, false),
+                AccountMeta::new_readonly(user_ata, false),
+                AccountMeta::new(user.pubkey(), false),
+                AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[b"plant_counter", plant_name.as_bytes()],
+                        &program_id,
+                    )
+                    .0,
+                    false,
+                ),
+                AccountMeta::new(page_pda, false),
+                AccountMeta::new(
+                    find_user_profile_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+            ],
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx =
+            Transaction::new_with_payer(std::slice::from_ref(&close_ix), Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        assert!(
+            banks_client.process_transaction(tx).await.is_err(),
+            "Closing a record for a held card should fail"
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[
+                create_associated_token_account(
+                    &payer.pubkey(),
+                    &friend.pubkey(),
+                    &primordial_mint.pubkey(),
+                    &token_program_id(),
+                ),
+                spl_token::instruction::transfer(
+                    &token_program_id(),
+                    &user_ata,
+                    &friend_ata,
+                    &user.pubkey(),
+                    &[],
+                    1,
+                )
+                .unwrap(),
+                close_ix,
+            ],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &user], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .is_none());
+        let page = CollectionPage::try_from_slice(
+            &banks_client
+                .get_account(page_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert!(!page.contains(&CardRarityInstruction::PrimordialRelic, 0));
+
+        let profile = UserProfile::try_from_slice(
+            &banks_client
+                .get_account(find_user_profile_address(&program_id, &user.pubkey()).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(profile.cards_of(&CardRarityInstruction::PrimordialRelic), 0);
+        assert_eq!(profile.first_discoveries, 1);
+
+        println!("✅ Stale record closed after the card was transferred away");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_card_moves_ownership_record() {
+        println!("\n🧪 TEST: TransferCard keeps the ownership record with the token");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let friend = Keypair::new();
+        let plant_name = "Magnolia";
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        // The token has to go to the recipient's ATA, not any account the sender names.
+        let mut ix = transfer_card_ix(
+            program_id,
+            &payer.pubkey(),
+            &user.pubkey(),
+            &friend.pubkey(),
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint.pubkey(),
+            token_program_id(),
+        );
+        ix.accounts[4] = AccountMeta::new(
+            get_associated_token_address(&user.pubkey(), &primordial_mint.pubkey()),
+            false,
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        transfer_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &friend.pubkey(),
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        let sender_ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        assert!(banks_client
+            .get_account(sender_ownership_pda)
+            .await
+            .unwrap()
+            .is_none());
+
+        let recipient_ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &friend.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        let record_account = banks_client
+            .get_account(recipient_ownership_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let record = OwnershipRecord::try_from_slice(&record_account.data).unwrap();
+        assert_eq!(record.owner, friend.pubkey());
+        assert_eq!(record.nft_mint, primordial_mint.pubkey());
+
+        let friend_token_account = banks_client
+            .get_account(get_associated_token_address(
+                &friend.pubkey(),
+                &primordial_mint.pubkey(),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            spl_token::state::Account::unpack(&friend_token_account.data)
+                .unwrap()
+                .amount,
+            1
+        );
+
+        let result = transfer_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &friend.pubkey(),
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint.pubkey(),
+        )
+        .await;
+        assert!(result.is_err(), "Sender no longer owns the card");
+
+        let primordial = CardRarityInstruction::PrimordialRelic as usize;
+        for (wallet, held, first_discoveries) in [(&user, 0, 1), (&friend, 1, 0)] {
+            let profile = UserProfile::try_from_slice(
+                &banks_client
+                    .get_account(find_user_profile_address(&program_id, &wallet.pubkey()).0)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data,
+            )
+            .unwrap();
+            assert_eq!(profile.cards_by_rarity[primordial], held);
+            assert_eq!(profile.first_discoveries, first_discoveries);
+        }
+
+        println!("✅ Record, token and profiles moved together");
+    }
+
+    #[tokio::test]
+    async fn test_soulbound_quiz_cards_are_frozen() {
+        println!("\n🧪 TEST: Soulbound AscendantSeal cards cannot be transferred");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let stranger =
+            create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        let result = update_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &stranger,
+            ConfigUpdate::SetSoulbound {
+                rarity: CardRarityInstruction::AscendantSeal,
+                enabled: true,
+            },
+        )
+        .await;
+        assert!(result.is_err(), "Only the admin can update the config");
+
+        update_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            ConfigUpdate::SetSoulbound {
+                rarity: CardRarityInstruction::AscendantSeal,
+                enabled: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        let (codex_mint, ascendent_mint) = setup_quiz_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+
+        let plant_name = "Orange";
+        let quiz_salt = [7u8; 32];
+        let user_salt = [9u8; 32];
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        for quiz_id in [21, 22] {
+            create_quiz(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                quiz_id,
+                plant_name,
+                b"ACDB",
+                &quiz_salt,
+                now + 60,
+                3,
+            )
+            .await;
+            commit_quiz_answers(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &user,
+                quiz_id,
+                b"ACDB",
+                &user_salt,
+            )
+            .await
+            .unwrap();
+        }
+
+        warp_clock(&mut context, now + 61).await;
+
+        // The second win mints into the already frozen account from the first one.
+        for quiz_id in [21, 22] {
+            settle_quiz(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                quiz_id,
+                b"ACDB",
+                &quiz_salt,
+            )
+            .await
+            .unwrap();
+            submit_quiz_result(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &user,
+                plant_name,
+                quiz_id,
+                b"ACDB",
+                &user_salt,
+                CardRarityInstruction::AscendantSeal,
+                &ascendent_mint,
+                &codex_mint,
+                &ascendent_mint,
+            )
+            .await
+            .unwrap();
+        }
+
+        let user_ata = get_associated_token_address(&user.pubkey(), &ascendent_mint.pubkey());
+        let token_account = context
+            .banks_client
+            .get_account(user_ata)
+            .await
+            .unwrap()
+            .unwrap();
+        let token_account = spl_token::state::Account::unpack(&token_account.data).unwrap();
+        assert!(token_account.is_frozen());
+        assert_eq!(token_account.amount, 2);
+
+        let buyer = Keypair::new();
+        let buyer_ata = get_associated_token_address(&buyer.pubkey(), &ascendent_mint.pubkey());
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[
+                create_associated_token_account(
+                    &payer.pubkey(),
+                    &buyer.pubkey(),
+                    &ascendent_mint.pubkey(),
+                    &token_program_id(),
+                ),
+                spl_token::instruction::transfer(
+                    &token_program_id(),
+                    &user_ata,
+                    &buyer_ata,
+                    &user.pubkey(),
+                    &[],
+                    1,
+                )
+                .unwrap(),
+            ],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &user], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "Frozen soulbound card should not transfer"
+        );
+
+        let burn = |quiz_id: u64| {
+            burn_card_ix(
+                program_id,
+                &user,
+                plant_name,
+                CardRarityInstruction::AscendantSeal,
+                Some(quiz_id),
+                &ascendent_mint.pubkey(),
+            )
+        };
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[burn(21)], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "A frozen card needs the mint authority PDA to burn"
+        );
+
+        // The holder burns alone; the program signs the thaw and refreeze.
+        let mut ix = burn(21);
+        ix.accounts.push(AccountMeta::new_readonly(
+            find_mint_authority_address(&program_id).0,
+            false,
+        ));
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let token_account = context
+            .banks_client
+            .get_account(user_ata)
+            .await
+            .unwrap()
+            .unwrap();
+        let token_account = spl_token::state::Account::unpack(&token_account.data).unwrap();
+        assert!(token_account.is_frozen(), "The remaining card stays frozen");
+        assert_eq!(token_account.amount, 1);
+
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let counter_account = context
+            .banks_client
+            .get_account(plant_counter_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let counter = PlantCounter::try_from_slice(&counter_account.data).unwrap();
+        assert_eq!(counter.burned_count, 1);
+
+        println!("✅ Soulbound cards stay with the winner");
+    }
+
+    #[tokio::test]
+    async fn test_token_2022_mint_with_metadata() {
+        println!("\n🧪 TEST: Token-2022 mints carry metadata and mint cards");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints_with_program(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                spl_token_2022::id(),
+                false,
+            )
+            .await;
+
+        let mint_account = banks_client
+            .get_account(primordial_mint.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(mint_account.owner, spl_token_2022::id());
+
+        let mint_state =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+        let pointer = mint_state.get_extension::<MetadataPointer>().unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(pointer.metadata_address),
+            Some(primordial_mint.pubkey())
+        );
+        let metadata = mint_state
+            .get_variable_len_extension::<TokenMetadata>()
+            .unwrap();
+        assert_eq!(metadata.name, "Rose");
+        assert_eq!(metadata.symbol, "PrimordialRelic");
+        assert_eq!(metadata.uri, "https://example.com/nft.json");
+        assert_eq!(
+            Option::<Pubkey>::from(metadata.update_authority),
+            Some(mint_authority.pubkey())
+        );
+
+        // The program owns the SPL authorities; the curator key cannot mint around it.
+        let mint_authority_pda = find_mint_authority_address(&program_id).0;
+        assert_eq!(
+            mint_state.base.mint_authority,
+            Some(mint_authority_pda).into()
+        );
+        assert_eq!(
+            mint_state.base.freeze_authority,
+            Some(mint_authority_pda).into()
+        );
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        mint_card_with_program(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            "Rose",
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+            spl_token_2022::id(),
+        )
+        .await;
+
+        let token_account = banks_client
+            .get_account(get_associated_token_address_with_program_id(
+                &user.pubkey(),
+                &primordial_mint.pubkey(),
+                &spl_token_2022::id(),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        let token =
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account.data)
+                .unwrap();
+        assert_eq!(token.base.amount, 1);
+
+        println!("✅ Token-2022 card minted with on-mint metadata");
+    }
+
+    #[tokio::test]
+    async fn test_token_2022_non_transferable_card() {
+        println!("\n🧪 TEST: Non-transferable Token-2022 cards cannot be transferred");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints_with_program(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &mint_authority,
+                spl_token_2022::id(),
+                true,
+            )
+            .await;
+
+        let mint_account = banks_client
+            .get_account(primordial_mint.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let mint_state =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+        assert!(mint_state.get_extension::<NonTransferable>().is_ok());
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let friend = Keypair::new();
+        let plant_name = "Magnolia";
+
+        mint_card_with_program(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+            spl_token_2022::id(),
+        )
+        .await;
+
+        let result = transfer_card_with_program(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &friend.pubkey(),
+            plant_name,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint.pubkey(),
+            spl_token_2022::id(),
+        )
+        .await;
+        assert!(result.is_err(), "Non-transferable cards must stay put");
+
+        let sender_ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::PrimordialRelic,
+            None,
+        )
+        .0;
+        assert!(banks_client
+            .get_account(sender_ownership_pda)
+            .await
+            .unwrap()
+            .is_some());
+
+        println!("✅ Non-transferable card and record stayed with the owner");
+    }
+
+    #[tokio::test]
+    async fn test_merkle_ownership_log() {
+        println!("\n🧪 TEST: Merkle log mode appends leaves instead of ownership records");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let tree_pda = find_ownership_tree_address(&program_id).0;
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::InitializeOwnershipTree
+                .try_to_vec()
+                .unwrap(),
+            vec![
+                AccountMeta::new_readonly(mint_authority.pubkey(), true),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(tree_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        update_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+            ConfigUpdate::SetMerkleLog { enabled: true },
+        )
+        .await
+        .unwrap();
+
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) = setup_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let plant_name = "Lotus";
+        let minted_at = 1_700_000_000;
+        warp_clock(&mut context, minted_at).await;
+
+        let mut leaves = Vec::new();
+        let mut last_mint = None;
+        for (rarity, mint) in [
+            (CardRarityInstruction::PrimordialRelic, &primordial_mint),
+            (CardRarityInstruction::MythicCrest, &epic_mint),
+        ] {
+            let user =
+                create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+            let ix = Instruction::new_with_bytes(
+                program_id,
+                &ProgramInstruction::MintNFT {
+                    card_type: CardRarityInstruction::GenesisFragment,
+                    plant_name: plant_name.to_string(),
+                    is_new_species: Some(false),
+                    context: None,
+                }
+                .try_to_vec()
+                .unwrap(),
+                vec![
+                    AccountMeta::new(user.pubkey(), true),
+                    AccountMeta::new(common_mint.pubkey(), false),
+                    AccountMeta::new(rare_mint.pubkey(), false),
+                    AccountMeta::new(epic_mint.pubkey(), false),
+                    AccountMeta::new(aurora_mint.pubkey(), false),
+                    AccountMeta::new(primordial_mint.pubkey(), false),
+                    AccountMeta::new(mint_authority.pubkey(), true),
+                    AccountMeta::new(
+                        get_associated_token_address(&user.pubkey(), &mint.pubkey()),
+                        false,
+                    ),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new_readonly(token_program_id(), false),
+                    AccountMeta::new(tree_pda, false),
+                    AccountMeta::new(
+                        Pubkey::find_program_address(
+                            &[b"plant_counter", plant_name.as_bytes()],
+                            &program_id,
+                        )
+                        .0,
+                        false,
+                    ),
+                    AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                    AccountMeta::new(
+                        find_user_profile_address(&program_id, &user.pubkey()).0,
+                        false,
+                    ),
+                    AccountMeta::new(find_plant_registry_address(&program_id).0, false),
+                    AccountMeta::new(
+                        find_collection_page_address(&program_id, &user.pubkey(), 0).0,
+                        false,
+                    ),
+                    AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                    AccountMeta::new(
+                        find_rate_limit_address(&program_id, &user.pubkey()).0,
+                        false,
+                    ),
+                    AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
+                    AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+                ],
+            );
+            let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+            let mut tx =
+                Transaction::new_with_payer(std::slice::from_ref(&ix), Some(&payer.pubkey()));
+            tx.sign(&[&payer, &user, &mint_authority], blockhash);
+            context.banks_client.process_transaction(tx).await.unwrap();
+            last_mint = Some((ix, user.insecure_clone()));
+
+            let ownership_pda = find_ownership_address(
+                &program_id,
+                plant_name,
+                &user.pubkey(),
+                rarity.clone(),
+                None,
+            )
+            .0;
+            assert!(context
+                .banks_client
+                .get_account(ownership_pda)
+                .await
+                .unwrap()
+                .is_none());
+
+            leaves.push((user.pubkey(), rarity));
+        }
+
+        // A second MythicCrest for the same user is caught by the collection page check.
+        let (ix, user) = last_mint.unwrap();
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user, &mint_authority], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "The same card cannot be logged twice for one user"
+        );
+
+        let tree_account = context
+            .banks_client
+            .get_account(tree_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let tree = OwnershipTree::try_from_slice(&tree_account.data).unwrap();
+        assert_eq!(tree.leaf_count, 2);
+
+        let leaf_hashes: Vec<[u8; 32]> = leaves
+            .iter()
+            .map(|(owner, rarity)| ownership_leaf(owner, plant_name, rarity, minted_at))
+            .collect();
+        let proof = build_proof(&leaf_hashes, 1);
+        assert_eq!(compute_root(leaf_hashes[1], 1, &proof), current_root(&tree));
+
+        let (owner, rarity) = leaves[1].clone();
+        let verify = |rarity: CardRarityInstruction, proof: Vec<[u8; 32]>| {
+            Instruction::new_with_bytes(
+                program_id,
+                &ProgramInstruction::VerifyOwnership {
+                    args: VerifyOwnershipArgs {
+                        owner,
+                        plant_name: plant_name.to_string(),
+                        rarity,
+                        timestamp: minted_at,
+                        leaf_index: 1,
+                        proof,
+                    },
+                }
+                .try_to_vec()
+                .unwrap(),
+                vec![AccountMeta::new_readonly(tree_pda, false)],
+            )
+        };
+
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx =
+            Transaction::new_with_payer(&[verify(rarity, proof.clone())], Some(&payer.pubkey()));
+        tx.sign(&[&payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let mut tx = Transaction::new_with_payer(
+            &[verify(CardRarityInstruction::AuroraSeed, proof)],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer], blockhash);
+        let result = context.banks_client.process_transaction(tx).await;
+        assert!(result.is_err(), "A proof for a different rarity must fail");
+
+        println!("✅ Ownership leaves appended and verified");
+    }
+
+    #[test]
+    fn test_event_round_trip() {
+        println!("\n🧪 TEST: Events survive the sol_log_data encoding");
+
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let events = vec![
+            BiodexEvent::CardMinted {
+                owner,
+                plant_name: "Rose".to_string(),
+                rarity: CardRarityInstruction::MythicCrest,
+                mint,
+                quiz_id: None,
+            },
+            BiodexEvent::PlantFirstDiscovered {
+                plant_name: "Rose".to_string(),
+                discoverer: owner,
+                new_species: false,
+            },
+            BiodexEvent::QuizCardAwarded {
+                quiz_id: 7,
+                participant: owner,
+                plant_name: "Rose".to_string(),
+                rarity: CardRarityInstruction::AscendantSeal,
+            },
+            BiodexEvent::CardBurned {
+                owner,
+                plant_name: "Rose".to_string(),
+                rarity: CardRarityInstruction::GenesisFragment,
+                mint,
+            },
+            BiodexEvent::CardTransferred {
+                from: owner,
+                to: Pubkey::new_unique(),
+                plant_name: "Rose".to_string(),
+                rarity: CardRarityInstruction::AstralShard,
+                mint,
+            },
+            BiodexEvent::OwnershipLeafAppended {
+                leaf_index: 3,
+                owner,
+                plant_name: "Rose".to_string(),
+                rarity: CardRarityInstruction::GenesisFragment,
+                timestamp: 1_700_000_000,
+            },
+        ];
+
+        let encode = |version: u8, event: &BiodexEvent| {
+            let fields = [
+                EVENT_TAG.to_vec(),
+                vec![version],
+                event.try_to_vec().unwrap(),
+            ];
+            let encoded: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+            format!("Program data: {}", encoded.join(" "))
+        };
+
+        // Another program logging the Biodex tag, both via CPI and at the top level.
+        let program_id = Pubkey::new_unique();
+        let impostor = Pubkey::new_unique();
+        let mut logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Minting NFT to associated token account...".to_string(),
+            format!("Program {} invoke [2]", impostor),
+            encode(EVENT_VERSION, &events[1]),
+            format!("Program {} success", impostor),
+            encode(EVENT_VERSION + 1, &events[0]),
+            "Program data: bm90IGJpb2RleA==".to_string(),
+        ];
+        logs.extend(events.iter().map(|event| encode(EVENT_VERSION, event)));
+        logs.extend([
+            format!(
+                "Program {} consumed 5000 of 200000 compute units",
+                program_id
+            ),
+            format!("Program {} success", program_id),
+            format!("Program {} invoke [1]", impostor),
+            encode(EVENT_VERSION, &events[0]),
+            format!("Program {} failed: custom program error: 0x0", impostor),
+        ]);
+
+        assert_eq!(decode_logs(&program_id, &logs), events);
+        assert_eq!(
+            decode_logs(&impostor, &logs),
+            vec![events[1].clone(), events[0].clone()]
+        );
+
+        println!("✅ All event variants decoded");
+    }
+
+    #[tokio::test]
+    async fn test_user_profile_tracks_collection() {
+        println!("\n🧪 TEST: UserProfile aggregates the user's collection");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let other = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        // First discovery of a known plant, then a new species.
+        for (minter, plant_name, is_new_species, rarity, mint) in [
+            (
+                &user,
+                "Rose",
+                false,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+            ),
+            (
+                &user,
+                "Lotus",
+                true,
+                CardRarityInstruction::AuroraSeed,
+                &aurora_mint,
+            ),
+            (
+                &other,
+                "Rose",
+                false,
+                CardRarityInstruction::MythicCrest,
+                &epic_mint,
+            ),
+            (
+                &other,
+                "Lotus",
+                false,
+                CardRarityInstruction::MythicCrest,
+                &epic_mint,
+            ),
+        ] {
+            mint_card(
+                &mut banks_client,
+                &payer,
+                program_id,
+                minter,
+                &mint_authority,
+                plant_name,
+                is_new_species,
+                rarity,
+                mint,
+                &common_mint,
+                &rare_mint,
+                &epic_mint,
+                &aurora_mint,
+                &primordial_mint,
+            )
+            .await;
+        }
+
+        let profile_account = banks_client
+            .get_account(find_user_profile_address(&program_id, &user.pubkey()).0)
+            .await
+            .unwrap()
+            .unwrap();
+        let profile = UserProfile::try_from_slice(&profile_account.data).unwrap();
+        assert_eq!(profile.user, user.pubkey());
+        assert_eq!(profile.total_cards(), 2);
+        assert_eq!(profile.cards_of(&CardRarityInstruction::PrimordialRelic), 1);
+        assert_eq!(profile.cards_of(&CardRarityInstruction::AuroraSeed), 1);
+        assert_eq!(profile.first_discoveries, 2);
+        assert_eq!(profile.quiz_wins, 0);
+        assert_eq!(
+            profile.plants,
+            vec![plant_hash("Rose"), plant_hash("Lotus")]
+        );
+
+        let other_account = banks_client
+            .get_account(find_user_profile_address(&program_id, &other.pubkey()).0)
+            .await
+            .unwrap()
+            .unwrap();
+        let other_profile = UserProfile::try_from_slice(&other_account.data).unwrap();
+        assert_eq!(
+            other_profile.cards_of(&CardRarityInstruction::MythicCrest),
+            2
+        );
+        assert_eq!(other_profile.first_discoveries, 0);
+        assert_eq!(other_profile.distinct_plants(), 2);
+
+        println!("✅ Profiles track rarities, first discoveries and distinct plants");
+    }
+
+    #[tokio::test]
+    async fn test_collection_page_tracks_cards() {
+        println!("\n🧪 TEST: Collection pages hold one bit per (rarity, plant)");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        for (plant_name, is_new_species, rarity, mint) in [
+            (
+                "Rose",
+                false,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+            ),
+            (
+                "Lotus",
+                true,
+                CardRarityInstruction::AuroraSeed,
+                &aurora_mint,
+            ),
+        ] {
+            mint_card(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &user,
+                &mint_authority,
+                plant_name,
+                is_new_species,
+                rarity,
+                mint,
+                &common_mint,
+                &rare_mint,
+                &epic_mint,
+                &aurora_mint,
+                &primordial_mint,
+            )
+            .await;
+        }
+
+        let registry_account = banks_client
+            .get_account(find_plant_registry_address(&program_id).0)
+            .await
+            .unwrap()
+            .unwrap();
+        let registry = PlantRegistry::try_from_slice(&registry_account.data).unwrap();
+        assert_eq!(registry.plant_count, 2);
+
+        let lotus_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", b"Lotus"], &program_id).0;
+        let lotus_counter = PlantCounter::try_from_slice(
+            &banks_client
+                .get_account(lotus_counter_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(lotus_counter.plant_id, 1);
+        assert_eq!(collection_page_index(lotus_counter.plant_id), 0);
+
+        let page_pda = find_collection_page_address(&program_id, &user.pubkey(), 0).0;
+        let page = CollectionPage::try_from_slice(
+            &banks_client
+                .get_account(page_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(page.user, user.pubkey());
+        assert!(page.contains(&CardRarityInstruction::PrimordialRelic, 0));
+        assert!(page.contains(&CardRarityInstruction::AuroraSeed, 1));
+        assert!(!page.contains(&CardRarityInstruction::AuroraSeed, 0));
+
+        burn_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            "Rose",
+            CardRarityInstruction::PrimordialRelic,
+            None,
+            &primordial_mint.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        let page = CollectionPage::try_from_slice(
+            &banks_client
+                .get_account(page_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert!(!page.contains(&CardRarityInstruction::PrimordialRelic, 0));
+        assert!(page.contains(&CardRarityInstruction::AuroraSeed, 1));
+
+        println!("✅ Minting sets the bit and burning clears it");
+    }
+
+    #[tokio::test]
+    async fn test_claim_set_reward() {
+        println!("\n🧪 TEST: ClaimSetReward needs a card of every plant in the set");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (reward_mint, _) =
+            setup_quiz_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            "Rose",
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        // Lotus gets plant id 1 once it is first minted, but the set can reference it up front.
+        let set_args = CreatePlantSetArgs {
+            set_id: 7,
+            name: "Sacred flowers".to_string(),
+            plant_ids: vec![0, 1],
+            reward_mint: reward_mint.pubkey(),
+        };
+        assert!(
+            create_plant_set(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &user,
+                set_args.clone()
+            )
+            .await
+            .is_err(),
+            "Only curators can create sets"
+        );
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &admin.pubkey(),
+            Role::Curator,
+        )
+        .await;
+        create_plant_set(&mut banks_client, &payer, program_id, &admin, set_args)
+            .await
+            .unwrap();
+
+        let cards = [
+            ("Rose", CardRarityInstruction::PrimordialRelic),
+            ("Lotus", CardRarityInstruction::AuroraSeed),
+        ];
+        let result = claim_set_reward(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            7,
+            &reward_mint.pubkey(),
+            &cards,
+        )
+        .await;
+        assert!(result.is_err(), "Claim must fail without a Lotus card");
+
+        mint_card(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            &mint_authority,
+            "Lotus",
+            true,
+            CardRarityInstruction::AuroraSeed,
+            &aurora_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        claim_set_reward(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            7,
+            &reward_mint.pubkey(),
+            &cards,
+        )
+        .await
+        .unwrap();
+
+        let token_account = banks_client
+            .get_account(get_associated_token_address(
+                &user.pubkey(),
+                &reward_mint.pubkey(),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            spl_token::state::Account::unpack(&token_account.data)
+                .unwrap()
+                .amount,
+            1
+        );
+
+        let set = PlantSet::try_from_slice(
+            &banks_client
+                .get_account(find_plant_set_address(&program_id, 7).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(set.claimed_count, 1);
+
+        let result = claim_set_reward(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            7,
+            &reward_mint.pubkey(),
+            &cards,
+        )
+        .await;
+        assert!(result.is_err(), "A set can only be claimed once");
+
+        println!("✅ Set reward minted once the set was complete");
+    }
+
+    #[tokio::test]
+    async fn test_fuse_genesis_fragments() {
+        println!("\n🧪 TEST: Fuse burns GenesisFragments into an AstralShard");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let plants = ["Tulip", "Fern"];
+
+        // Exhaust the relic, epic and rare slots of both plants so `user` gets GenesisFragments.
+        for plant_name in plants {
+            let mut expected = vec![(CardRarityInstruction::PrimordialRelic, &primordial_mint)];
+            expected.extend((0..5).map(|_| (CardRarityInstruction::MythicCrest, &epic_mint)));
+            expected.extend((0..10).map(|_| (CardRarityInstruction::AstralShard, &rare_mint)));
+            for (rarity, mint) in expected {
+                let filler = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+                mint_card(
+                    &mut banks_client,
+                    &payer,
+                    program_id,
+                    &filler,
+                    &mint_authority,
+                    plant_name,
+                    false,
+                    rarity,
+                    mint,
+                    &common_mint,
+                    &rare_mint,
+                    &epic_mint,
+                    &aurora_mint,
+                    &primordial_mint,
+                )
+                .await;
+            }
+
+            mint_card(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &user,
+                &mint_authority,
+                plant_name,
+                false,
+                CardRarityInstruction::GenesisFragment,
+                &common_mint,
+                &common_mint,
+                &rare_mint,
+                &epic_mint,
+                &aurora_mint,
+                &primordial_mint,
+            )
+            .await;
+        }
+
+        let args = FuseArgs {
+            input: CardRarityInstruction::GenesisFragment,
+            input_plants: plants.iter().map(|plant| plant.to_string()).collect(),
+            output_plant: "Tulip".to_string(),
+        };
+        let mints = (&common_mint.pubkey(), &rare_mint.pubkey());
+
+        let result = fuse_cards(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            args.clone(),
+            mints,
+            CardRarityInstruction::AstralShard,
+        )
+        .await;
+        assert!(result.is_err(), "Fusion needs a configured recipe");
+
+        assert!(update_config(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetFusionRecipe {
+                input: CardRarityInstruction::AstralShard,
+                output: CardRarityInstruction::GenesisFragment,
+                input_count: 2,
+            },
+        )
+        .await
+        .is_err());
+        update_config(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetFusionRecipe {
+                input: CardRarityInstruction::GenesisFragment,
+                output: CardRarityInstruction::AstralShard,
+                input_count: 2,
+            },
+        )
+        .await
+        .unwrap();
+
+        fuse_cards(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &user,
+            args,
+            mints,
+            CardRarityInstruction::AstralShard,
+        )
+        .await
+        .unwrap();
+
+        for (mint, amount) in [(&common_mint, 0), (&rare_mint, 1)] {
+            let token_account = banks_client
+                .get_account(get_associated_token_address(&user.pubkey(), &mint.pubkey()))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                spl_token::state::Account::unpack(&token_account.data)
+                    .unwrap()
+                    .amount,
+                amount
+            );
+        }
+
+        for plant_name in plants {
+            let genesis_pda = find_ownership_address(
+                &program_id,
+                plant_name,
+                &user.pubkey(),
+                CardRarityInstruction::GenesisFragment,
+                None,
+            )
+            .0;
+            assert!(banks_client
+                .get_account(genesis_pda)
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        let shard_account = banks_client
+            .get_account(
+                find_ownership_address(
+                    &program_id,
+                    "Tulip",
+                    &user.pubkey(),
+                    CardRarityInstruction::AstralShard,
+                    None,
+                )
+                .0,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        let shard = OwnershipRecord::try_from_slice(&shard_account.data).unwrap();
+        assert_eq!(shard.rarity, CardRarityInstruction::AstralShard);
+        assert_eq!(shard.nft_mint, rare_mint.pubkey());
+
+        let tulip_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", b"Tulip"], &program_id).0;
+        let tulip_counter = PlantCounter::try_from_slice(
+            &banks_client
+                .get_account(tulip_counter_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(tulip_counter.burned_count, 1);
+        assert_eq!(tulip_counter.fused_count, 1);
+
+        println!("✅ Two GenesisFragments fused into a Tulip AstralShard");
+    }
+
+    #[tokio::test]
+    async fn test_random_rarity_commit_reveal() {
+        println!("\n🧪 TEST: RequestMint + SettleMint draw the rarity from a later slot hash");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        initialize_config(&mut context.banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority =
+            create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) = setup_mints(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &mint_authority,
+        )
+        .await;
+
+        let plant_name = "Rose";
+        let discoverer =
+            create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        mint_card(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &discoverer,
+            &mint_authority,
+            plant_name,
+            false,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        )
+        .await;
+
+        // Every draw lands on MythicCrest, so the outcome does not depend on the slot hash.
+        update_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetRarityOdds {
+                odds: Some(RarityOdds {
+                    mythic_bps: 10_000,
+                    astral_bps: 0,
+                }),
+            },
+        )
+        .await
+        .unwrap();
+
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        let seed = [42u8; 32];
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let page_pda = find_collection_page_address(&program_id, &user.pubkey(), 0).0;
+        let request_pda = find_mint_request_address(&program_id, plant_name, &user.pubkey()).0;
+
+        let request_ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::RequestMint {
+                plant_name: plant_name.to_string(),
+                commitment: mint_commitment(&user.pubkey(), &seed),
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new_readonly(plant_counter_pda, false),
+                AccountMeta::new_readonly(page_pda, false),
+                AccountMeta::new(request_pda, false),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[request_ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let ownership_pda = find_ownership_address(
+            &program_id,
+            plant_name,
+            &user.pubkey(),
+            CardRarityInstruction::MythicCrest,
+            None,
+        )
+        .0;
+        let settle_ix = |seed: [u8; 32]| {
+            Instruction::new_with_bytes(
+                program_id,
+                &ProgramInstruction::SettleMint {
+                    plant_name: plant_name.to_string(),
+                    seed,
+                    context: None,
+                }
+                .try_to_vec()
+                .unwrap(),
+                vec![
+                    AccountMeta::new(user.pubkey(), false),
+                    AccountMeta::new(common_mint.pubkey(), false),
+                    AccountMeta::new(rare_mint.pubkey(), false),
+                    AccountMeta::new(epic_mint.pubkey(), false),
+                    AccountMeta::new(aurora_mint.pubkey(), false),
+                    AccountMeta::new(primordial_mint.pubkey(), false),
+                    AccountMeta::new(mint_authority.pubkey(), true),
+                    AccountMeta::new(
+                        get_associated_token_address(&user.pubkey(), &epic_mint.pubkey()),
+                        false,
+                    ),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new_readonly(token_program_id(), false),
+                    AccountMeta::new(ownership_pda, false),
+                    AccountMeta::new(plant_counter_pda, false),
+                    AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                    AccountMeta::new(
+                        find_user_profile_address(&program_id, &user.pubkey()).0,
+                        false,
+                    ),
+                    AccountMeta::new(find_plant_registry_address(&program_id).0, false),
+                    AccountMeta::new(page_pda, false),
+                    AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                    AccountMeta::new(
+                        find_rate_limit_address(&program_id, &user.pubkey()).0,
+                        false,
+                    ),
+                    AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
+                    AccountMeta::new(request_pda, false),
+                    AccountMeta::new_readonly(solana_sdk::sysvar::slot_hashes::id(), false),
+                    AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+                ],
+            )
+        };
+
+        // No slot after the commit has been hashed yet.
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[settle_ix(seed)], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        assert!(context.banks_client.process_transaction(tx).await.is_err());
+
+        // Each warp freezes the current bank, so after two of them a later slot hash exists.
+        for _ in 0..2 {
+            let slot = context.banks_client.get_root_slot().await.unwrap();
+            context.warp_to_slot(slot + 2).unwrap();
+        }
+
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[settle_ix([7u8; 32])], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "A different seed must not match the commitment"
+        );
+
+        let mut tx = Transaction::new_with_payer(&[settle_ix(seed)], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &mint_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let record_account = context
+            .banks_client
+            .get_account(ownership_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let record = OwnershipRecord::try_from_slice(&record_account.data).unwrap();
+        assert_eq!(record.rarity, CardRarityInstruction::MythicCrest);
+        assert!(context
+            .banks_client
+            .get_account(request_pda)
+            .await
+            .unwrap()
+            .is_none());
+
+        println!("✅ Drawn MythicCrest settled after the commit slot");
+    }
+
+    #[tokio::test]
+    async fn test_season_raises_caps_and_awards_blooms() {
+        println!("\n🧪 TEST: An active season raises caps and hands out SeasonalBloom cards");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (bloom_mint, _) =
+            setup_quiz_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mints = [
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        ];
+
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &admin.pubkey(),
+            Role::Curator,
+        )
+        .await;
+        let season_pda = find_season_address(&program_id, 1).0;
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &ProgramInstruction::CreateSeason {
+                args: CreateSeasonArgs {
+                    season_id: 1,
+                    name: "Monsoon bloom week".to_string(),
+                    starts_at: 0,
+                    ends_at: i64::MAX,
+                    plants: vec!["Rose".to_string()],
+                    epic_cap_bonus: 1,
+                    rare_cap_bonus: 0,
+                    odds: None,
+                    odds_decay: false,
+                    bloom_mint: bloom_mint.pubkey(),
+                    bloom_supply: 1,
+                },
+            }
+            .try_to_vec()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(admin.pubkey(), true),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(season_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &admin], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        update_config(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetActiveSeason { season_id: Some(1) },
+        )
+        .await
+        .unwrap();
+
+        // 1 relic, 5 + 1 epic thanks to the season, 10 rare, then the single bloom.
+        let mut expected = vec![(CardRarityInstruction::PrimordialRelic, &primordial_mint)];
+        expected.extend((0..6).map(|_| (CardRarityInstruction::MythicCrest, &epic_mint)));
+        expected.extend((0..10).map(|_| (CardRarityInstruction::AstralShard, &rare_mint)));
+        expected.push((CardRarityInstruction::SeasonalBloom, &bloom_mint));
+        expected.push((CardRarityInstruction::GenesisFragment, &common_mint));
+
+        for (rarity, mint) in expected {
+            let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+            let mut ix = mint_nft_ix(
+                program_id,
+                &payer,
+                &user,
+                &mint_authority,
+                "Rose",
+                false,
+                rarity.clone(),
+                mint,
+                mints,
+                token_program_id(),
+            );
+            ix.accounts.insert(21, AccountMeta::new(season_pda, false));
+            ix.accounts
+                .insert(22, AccountMeta::new(bloom_mint.pubkey(), false));
+
+            let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+            let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+            tx.sign(&[&payer, &user, &mint_authority], blockhash);
+            banks_client.process_transaction(tx).await.unwrap();
+
+            let record_account = banks_client
+                .get_account(
+                    find_ownership_address(
+                        &program_id,
+                        "Rose",
+                        &user.pubkey(),
+                        rarity.clone(),
+                        None,
+                    )
+                    .0,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            let record = OwnershipRecord::try_from_slice(&record_account.data).unwrap();
+            assert_eq!(record.rarity, rarity);
+        }
+
+        let season = Season::try_from_slice(
+            &banks_client
+                .get_account(season_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(season.blooms_minted, 1);
+        assert!(!season.blooms_left());
+
+        println!("✅ Season added an epic slot and awarded its only bloom");
+    }
+
+    #[tokio::test]
+    async fn test_discovery_context_stored_on_record() {
+        println!("\n🧪 TEST: MintNFT stores the attested discovery context");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (regional_mint, _) =
+            setup_quiz_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mints = [
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        ];
+
+        let context = DiscoveryContext {
+            geohash: Some("tuvz4".to_string()),
+            region_id: Some(977),
+            image_hash: Some([7; 32]),
+        };
+        let mint_with_context = |user: &Keypair,
+                                 rarity: CardRarityInstruction,
+                                 mint: &Keypair,
+                                 context: DiscoveryContext| {
+            let mut ix = mint_nft_ix(
+                program_id,
+                &payer,
+                user,
+                &mint_authority,
+                "Rose",
+                false,
+                rarity,
+                mint,
+                mints,
+                token_program_id(),
+            );
+            // A region_id needs the trailing region counter and RegionalRelic mint.
+            if let Some(region_id) = context.region_id {
+                ix.accounts.insert(
+                    21,
+                    AccountMeta::new(
+                        find_region_counter_address(&program_id, "Rose", region_id).0,
+                        false,
+                    ),
+                );
+                ix.accounts
+                    .insert(22, AccountMeta::new(regional_mint.pubkey(), false));
+            }
+            ix.data = ProgramInstruction::MintNFT {
+                card_type: CardRarityInstruction::GenesisFragment,
+                plant_name: "Rose".to_string(),
+                is_new_species: Some(false),
+                context: Some(context),
+            }
+            .try_to_vec()
+            .unwrap();
+            ix
+        };
+
+        let finder = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let ix = mint_with_context(
+            &finder,
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            context.clone(),
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &finder, &mint_authority], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let record_account = banks_client
+            .get_account(
+                find_ownership_address(
+                    &program_id,
+                    "Rose",
+                    &finder.pubkey(),
+                    CardRarityInstruction::PrimordialRelic,
+                    None,
+                )
+                .0,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        let record = OwnershipRecord::try_from_slice(&record_account.data).unwrap();
+        assert_eq!(record.context, Some(context));
+
+        // Anything finer than a 5 character geohash is rejected.
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let ix = mint_with_context(
+            &user,
+            CardRarityInstruction::MythicCrest,
+            &epic_mint,
+            DiscoveryContext {
+                geohash: Some("tuvz4p".to_string()),
+                ..DiscoveryContext::default()
+            },
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user, &mint_authority], blockhash);
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        println!("✅ Context stored and over-precise geohash rejected");
+    }
+
+    #[tokio::test]
+    async fn test_regional_relic_for_first_in_region() {
+        println!("\n🧪 TEST: The first discoverer in each region gets a RegionalRelic");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (regional_mint, _) =
+            setup_quiz_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mints = [
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        ];
+
+        let region_ix =
+            |user: &Keypair, rarity: CardRarityInstruction, mint: &Keypair, region_id: u32| {
+                let mut ix = mint_nft_ix(
+                    program_id,
+                    &payer,
+                    user,
+                    &mint_authority,
+                    "Rose",
+                    false,
+                    rarity,
+                    mint,
+                    mints,
+                    token_program_id(),
+                );
+                ix.data = ProgramInstruction::MintNFT {
+                    card_type: CardRarityInstruction::GenesisFragment,
+                    plant_name: "Rose".to_string(),
+                    is_new_species: Some(false),
+                    context: Some(DiscoveryContext {
+                        region_id: Some(region_id),
+                        ..DiscoveryContext::default()
+                    }),
+                }
+                .try_to_vec()
+                .unwrap();
+                ix.accounts.insert(
+                    21,
+                    AccountMeta::new(
+                        find_region_counter_address(&program_id, "Rose", region_id).0,
+                        false,
+                    ),
+                );
+                ix.accounts
+                    .insert(22, AccountMeta::new(regional_mint.pubkey(), false));
+                ix
+            };
+
+        // The global first discoverer opens region 1 without an extra card.
+        let finder = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let neighbour = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let traveller = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let mints_in_order = [
+            (
+                &finder,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+                1,
+            ),
+            (
+                &neighbour,
+                CardRarityInstruction::MythicCrest,
+                &epic_mint,
+                1,
+            ),
+            (
+                &traveller,
+                CardRarityInstruction::RegionalRelic,
+                &regional_mint,
+                2,
+            ),
+            // One RegionalRelic per plant, so region 3 stays open for someone else.
+            (
+                &traveller,
+                CardRarityInstruction::MythicCrest,
+                &epic_mint,
+                3,
+            ),
+        ];
+
+        for (user, rarity, mint, region_id) in mints_in_order {
+            let ix = region_ix(user, rarity.clone(), mint, region_id);
+            let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+            let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+            tx.sign(&[&payer, user, &mint_authority], blockhash);
+            banks_client.process_transaction(tx).await.unwrap();
+
+            let record_account = banks_client
+                .get_account(
+                    find_ownership_address(
+                        &program_id,
+                        "Rose",
+                        &user.pubkey(),
+                        rarity.clone(),
+                        None,
+                    )
+                    .0,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            let record = OwnershipRecord::try_from_slice(&record_account.data).unwrap();
+            assert_eq!(record.rarity, rarity);
+        }
+
+        let region_counter =
+            |region_id: u32| find_region_counter_address(&program_id, "Rose", region_id).0;
+        for (region_id, first_minter, card_count) in [
+            (1, Some(finder.pubkey()), 2),
+            (2, Some(traveller.pubkey()), 1),
+            (3, None, 1),
+        ] {
+            let counter = RegionCounter::try_from_slice(
+                &banks_client
+                    .get_account(region_counter(region_id))
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data,
+            )
+            .unwrap();
+            assert_eq!(counter.first_minter, first_minter);
+            assert_eq!(counter.card_count, card_count);
+        }
+
+        println!("✅ Regional relics awarded once per region and per user");
+    }
+
+    #[test]
+    fn test_discovery_state_transitions() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let known = DiscoveryState::Discovered {
+            kind: DiscoveryKind::KnownSpecies,
+            by: first,
+            at: 10,
+        };
+        let new = DiscoveryState::Discovered {
+            kind: DiscoveryKind::NewSpecies,
+            by: first,
+            at: 10,
+        };
+
+        // (state before, new species claim, card awarded, state after)
+        let transitions = [
+            (
+                DiscoveryState::Undiscovered,
+                false,
+                Some(DiscoveryKind::KnownSpecies),
+                known.clone(),
+            ),
+            (
+                DiscoveryState::Undiscovered,
+                true,
+                Some(DiscoveryKind::NewSpecies),
+                new.clone(),
+            ),
+            (known.clone(), false, None, known.clone()),
+            (
+                known.clone(),
+                true,
+                Some(DiscoveryKind::NewSpecies),
+                DiscoveryState::Discovered {
+                    kind: DiscoveryKind::NewSpecies,
+                    by: second,
+                    at: 20,
+                },
+            ),
+            (new.clone(), false, None, new.clone()),
+            (new.clone(), true, None, new.clone()),
+        ];
+
+        for (mut state, is_new_species, awarded, after) in transitions {
+            let (by, at) = if state == DiscoveryState::Undiscovered {
+                (first, 10)
+            } else {
+                (second, 20)
+            };
+            assert_eq!(state.discover(is_new_species, by, at), awarded);
+            assert_eq!(state, after);
+        }
+    }
+
+    #[test]
+    fn test_season_odds_decay() {
+        let base = RarityOdds {
+            mythic_bps: 100,
+            astral_bps: 1_000,
+        };
+        let season = |starts_at: i64, ends_at: i64| Season {
+            season_id: 1,
+            name: "Spring".to_string(),
+            starts_at,
+            ends_at,
+            plants: vec![],
+            epic_cap_bonus: 0,
+            rare_cap_bonus: 0,
+            odds: Some(RarityOdds {
+                mythic_bps: 500,
+                astral_bps: 200,
+            }),
+            odds_decay: true,
+            bloom_mint: Pubkey::new_unique(),
+            bloom_supply: 0,
+            blooms_minted: 0,
+        };
+        let odds = |mythic_bps: u16, astral_bps: u16| {
+            Some(RarityOdds {
+                mythic_bps,
+                astral_bps,
+            })
+        };
+
+        let spring = season(0, 100);
+        assert_eq!(spring.odds_at(-10, Some(&base)), odds(500, 200));
+        assert_eq!(spring.odds_at(50, Some(&base)), odds(300, 600));
+        assert_eq!(spring.odds_at(1_000, Some(&base)), odds(100, 1_000));
+        assert_eq!(spring.odds_at(50, None), odds(250, 100));
+
+        // Extreme timestamps must not overflow.
+        let endless = season(i64::MIN, i64::MAX);
+        assert_eq!(endless.odds_at(i64::MIN, Some(&base)), odds(500, 200));
+        assert_eq!(endless.odds_at(i64::MAX, Some(&base)), odds(100, 1_000));
+        assert_eq!(endless.odds_at(0, Some(&base)), odds(300, 600));
+    }
+
+    #[tokio::test]
+    async fn test_known_plant_reclassified_as_new_species() {
+        println!("\n🧪 TEST: A known plant can be reclassified once as a new species");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+
+        let finder = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let botanist = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let latecomer = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        for (user, is_new_species, rarity, mint) in [
+            (
+                &finder,
+                false,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+            ),
+            (
+                &botanist,
+                true,
+                CardRarityInstruction::AuroraSeed,
+                &aurora_mint,
+            ),
+            (
+                &latecomer,
+                true,
+                CardRarityInstruction::MythicCrest,
+                &epic_mint,
+            ),
+        ] {
+            mint_card(
+                &mut banks_client,
+                &payer,
+                program_id,
+                user,
+                &mint_authority,
+                "Rose",
+                is_new_species,
+                rarity,
+                mint,
+                &common_mint,
+                &rare_mint,
+                &epic_mint,
+                &aurora_mint,
+                &primordial_mint,
+            )
+            .await;
+        }
+
+        let plant_counter_pda =
+            Pubkey::find_program_address(&[b"plant_counter", b"Rose"], &program_id).0;
+        let counter = PlantCounter::try_from_slice(
+            &banks_client
+                .get_account(plant_counter_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(counter.relic_count, 1);
+        assert_eq!(counter.seed_count, 1);
+        assert_eq!(counter.epic_count, 1);
+        assert!(matches!(
+            counter.discovery,
+            DiscoveryState::Discovered {
+                kind: DiscoveryKind::NewSpecies,
+                by,
+                ..
+            } if by == botanist.pubkey()
+        ));
+
+        println!("✅ PrimordialRelic, then AuroraSeed on reclassification, then regular cards");
+    }
+
+    #[tokio::test]
+    async fn test_species_claim_review() {
+        println!("\n🧪 TEST: New species claims are approved or rejected by a reviewer");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        let reviewer = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+        update_config(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetSpeciesReview { enabled: true },
+        )
+        .await
+        .unwrap();
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &reviewer.pubkey(),
+            Role::Reviewer,
+        )
+        .await;
+
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mints = [
+            &common_mint,
+            &rare_mint,
+            &epic_mint,
+            &aurora_mint,
+            &primordial_mint,
+        ];
+        let botanist = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let hopeful = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        // With species review on, MintNFT no longer accepts new species claims.
+        let ix = mint_nft_ix(
+            program_id,
+            &payer,
+            &botanist,
+            &mint_authority,
+            "Lotus",
+            true,
+            CardRarityInstruction::AuroraSeed,
+            &aurora_mint,
+            mints,
+            token_program_id(),
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &botanist, &mint_authority], blockhash);
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        submit_species_claim(&mut banks_client, &payer, program_id, &botanist, "Lotus")
+            .await
+            .unwrap();
+        submit_species_claim(&mut banks_client, &payer, program_id, &hopeful, "Rose")
+            .await
+            .unwrap();
+
+        let review = |user: &Keypair,
+                      plant_name: &str,
+                      rarity: CardRarityInstruction,
+                      mint: &Keypair,
+                      instruction: ProgramInstruction,
+                      signer: &Keypair,
+                      blockhash: Hash| {
+            let ix = review_claim_ix(
+                program_id,
+                signer,
+                plant_name,
+                &user.pubkey(),
+                instruction,
+                mint_nft_ix(
+                    program_id,
+                    &payer,
+                    user,
+                    &mint_authority,
+                    plant_name,
+                    false,
+                    rarity,
+                    mint,
+                    mints,
+                    token_program_id(),
+                ),
+            );
+            let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+            tx.sign(&[&payer, signer, &mint_authority], blockhash);
+            tx
+        };
+
+        // Only reviewers can approve.
+        let approve_lotus = ProgramInstruction::ApproveClaim {
+            plant_name: "Lotus".to_string(),
+            user: botanist.pubkey(),
+        };
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let tx = review(
+            &botanist,
+            "Lotus",
+            CardRarityInstruction::AuroraSeed,
+            &aurora_mint,
+            approve_lotus.clone(),
+            &admin,
+            blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let tx = review(
+            &botanist,
+            "Lotus",
+            CardRarityInstruction::AuroraSeed,
+            &aurora_mint,
+            approve_lotus,
+            &reviewer,
+            blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // A rejected claim on an undiscovered plant still earns the known species relic.
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let tx = review(
+            &hopeful,
+            "Rose",
+            CardRarityInstruction::PrimordialRelic,
+            &primordial_mint,
+            ProgramInstruction::RejectClaim {
+                plant_name: "Rose".to_string(),
+                user: hopeful.pubkey(),
+                mint_regular: true,
+            },
+            &reviewer,
+            blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        for (user, plant_name, rarity) in [
+            (&botanist, "Lotus", CardRarityInstruction::AuroraSeed),
+            (&hopeful, "Rose", CardRarityInstruction::PrimordialRelic),
+        ] {
+            assert!(banks_client
+                .get_account(
+                    find_ownership_address(&program_id, plant_name, &user.pubkey(), rarity, None).0
+                )
+                .await
+                .unwrap()
+                .is_some());
+            assert!(banks_client
+                .get_account(find_species_claim_address(&program_id, plant_name, &user.pubkey()).0)
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        // Lotus is now a recorded new species, so further claims are refused up front.
+        assert!(
+            submit_species_claim(&mut banks_client, &payer, program_id, &hopeful, "Lotus")
+                .await
+                .is_err()
+        );
+
+        println!("✅ Claims reviewed, AuroraSeed on approval and a regular card on rejection");
+    }
+
+    #[tokio::test]
+    async fn test_species_claim_counts_once_against_daily_limit() {
+        println!("\n🧪 TEST: A reviewed species claim uses a single daily mint");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+        for update in [
+            ConfigUpdate::SetSpeciesReview { enabled: true },
+            ConfigUpdate::SetDailyMintLimit { limit: Some(1) },
+        ] {
+            update_config(&mut banks_client, &payer, program_id, &admin, update)
+                .await
+                .unwrap();
+        }
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &admin.pubkey(),
+            Role::Reviewer,
+        )
+        .await;
+
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &admin).await;
+        let botanist = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        submit_species_claim(&mut banks_client, &payer, program_id, &botanist, "Lotus")
+            .await
+            .unwrap();
+
+        let ix = review_claim_ix(
+            program_id,
+            &admin,
+            "Lotus",
+            &botanist.pubkey(),
+            ProgramInstruction::ApproveClaim {
+                plant_name: "Lotus".to_string(),
+                user: botanist.pubkey(),
+            },
+            mint_nft_ix(
+                program_id,
+                &payer,
+                &botanist,
+                &admin,
+                "Lotus",
+                false,
+                CardRarityInstruction::AuroraSeed,
+                &aurora_mint,
+                [
+                    &common_mint,
+                    &rare_mint,
+                    &epic_mint,
+                    &aurora_mint,
+                    &primordial_mint,
+                ],
+                token_program_id(),
+            ),
+        );
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &admin], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let rate_limit = MintRateLimit::try_from_slice(
+            &banks_client
+                .get_account(find_rate_limit_address(&program_id, &botanist.pubkey()).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert_eq!(rate_limit.recent_mints.len(), 1);
+
+        println!("✅ Approval succeeded within a daily limit of one");
+    }
+
+    #[tokio::test]
+    async fn test_role_grants_and_revokes() {
+        println!("\n🧪 TEST: Roles gate instructions and revoking a role takes access away");
+
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let (mut banks_client, payer, _) = program_test.start().await;
+
+        let curator = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let quiz_master = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+
+        let set_args = |set_id: u64| CreatePlantSetArgs {
+            set_id,
+            name: "Herbs".to_string(),
+            plant_ids: vec![0],
+            reward_mint: Pubkey::new_unique(),
+        };
+
+        assert!(
+            update_role(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &curator,
+                ProgramInstruction::GrantRole {
+                    member: curator.pubkey(),
+                    role: Role::Curator,
+                },
+            )
+            .await
+            .is_err(),
+            "Only the admin can grant roles"
+        );
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &curator.pubkey(),
+            Role::Curator,
+        )
+        .await;
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &quiz_master.pubkey(),
+            Role::QuizMaster,
+        )
+        .await;
+
+        create_plant_set(&mut banks_client, &payer, program_id, &curator, set_args(1))
+            .await
+            .unwrap();
+        assert!(
+            create_plant_set(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &quiz_master,
+                set_args(2)
+            )
+            .await
+            .is_err(),
+            "A quiz master is not a curator"
+        );
+        create_quiz(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &quiz_master,
+            1,
+            "Basil",
+            &[1, 2, 3],
+            &[9u8; 32],
+            i64::MAX,
+            1,
+        )
+        .await;
+        assert!(
+            try_create_quiz(
+                &mut banks_client,
+                &payer,
+                program_id,
+                &curator,
+                2,
+                "Basil",
+                &[1, 2, 3],
+                &[9u8; 32],
+                i64::MAX,
+                1,
+            )
+            .await
+            .is_err(),
+            "A curator is not a quiz master"
+        );
+
+        update_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ProgramInstruction::RevokeRole {
+                member: curator.pubkey(),
+                role: Role::Curator,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(
+            create_plant_set(&mut banks_client, &payer, program_id, &curator, set_args(3))
+                .await
+                .is_err(),
+            "A revoked curator cannot create sets"
+        );
+
+        let registry = RoleRegistry::try_from_slice(
+            &banks_client
+                .get_account(find_role_registry_address(&program_id).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert!(!registry.has_role(&curator.pubkey(), Role::Curator));
+        assert!(registry.has_role(&quiz_master.pubkey(), Role::QuizMaster));
+        // InitializeConfig hands the admin the roles the minting flow needs.
+        assert!(registry.has_role(&admin.pubkey(), Role::Curator));
+        assert!(registry.has_role(&admin.pubkey(), Role::QuizMaster));
+        assert!(registry.has_role(&admin.pubkey(), Role::AttestationOracle));
+
+        println!("✅ Curator and quiz master roles are enforced independently");
+    }
 
-        let card_type = if quiz {
-            CardRarityInstruction::AscendantSeal
-        } else {
-            CardRarityInstruction::CodexOfInsight
-        };
+    #[tokio::test]
+    async fn test_multisig_proposal_flow() {
+        println!("\n🧪 TEST: Proposals run as the multisig authority after M-of-N approvals and a timelock");
 
-        let plant_counter_pda =
-            Pubkey::find_program_address(&[b"plant_counter", plant_name.as_bytes()], &program_id).0;
+        let program_id = Pubkey::new_unique();
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
 
-        let ix = Instruction::new_with_bytes(
+        let approvers = [Keypair::new(), Keypair::new(), Keypair::new()];
+        let outsider = Keypair::new();
+        let config_pda = find_config_address(&program_id).0;
+        let authority_pda = find_multisig_authority_address(&program_id).0;
+        let proposal_pda = find_proposal_address(&program_id, 1).0;
+
+        initialize_config(&mut context.banks_client, &payer, program_id, &admin).await;
+        for update in [
+            ConfigUpdate::SetMultisig {
+                multisig: Some(Multisig {
+                    approvers: approvers.iter().map(|approver| approver.pubkey()).collect(),
+                    threshold: 2,
+                    timelock: 100,
+                }),
+            },
+            ConfigUpdate::SetAdmin {
+                admin: authority_pda,
+            },
+        ] {
+            update_config(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &admin,
+                update,
+            )
+            .await
+            .unwrap();
+        }
+        assert!(
+            update_config(
+                &mut context.banks_client,
+                &payer,
+                program_id,
+                &admin,
+                ConfigUpdate::SetMerkleLog { enabled: true },
+            )
+            .await
+            .is_err(),
+            "The old admin key no longer controls the config"
+        );
+
+        let proposal_accounts = |signer: &Keypair| {
+            vec![
+                AccountMeta::new_readonly(signer.pubkey(), true),
+                AccountMeta::new_readonly(config_pda, false),
+                AccountMeta::new(proposal_pda, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ]
+        };
+        let update_accounts = vec![
+            ProposalAccount {
+                pubkey: authority_pda,
+                is_signer: true,
+                is_writable: false,
+            },
+            ProposalAccount {
+                pubkey: config_pda,
+                is_signer: false,
+                is_writable: true,
+            },
+            ProposalAccount {
+                pubkey: payer.pubkey(),
+                is_signer: true,
+                is_writable: true,
+            },
+            ProposalAccount {
+                pubkey: system_program::id(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+        let create_ix = |proposer: &Keypair| {
+            Instruction::new_with_bytes(
+                program_id,
+                &ProgramInstruction::CreateProposal {
+                    proposal_id: 1,
+                    instruction: ProgramInstruction::UpdateConfig {
+                        update: ConfigUpdate::SetMerkleLog { enabled: true },
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                    accounts: update_accounts.clone(),
+                }
+                .try_to_vec()
+                .unwrap(),
+                proposal_accounts(proposer),
+            )
+        };
+        let approve_ix = |approver: &Keypair| {
+            Instruction::new_with_bytes(
+                program_id,
+                &ProgramInstruction::ApproveProposal { proposal_id: 1 }
+                    .try_to_vec()
+                    .unwrap(),
+                proposal_accounts(approver),
+            )
+        };
+        // Proposal accounts first, then the accounts of the proposed UpdateConfig.
+        let execute_ix = Instruction::new_with_bytes(
             program_id,
-            &ProgramInstruction::MintNFT {
-                card_type,
-                plant_name: plant_name.to_string(),
-                is_new_species: Some(false),
-                quiz_winner: Some(quiz),
-            }
-            .try_to_vec()
-            .unwrap(),
+            &ProgramInstruction::ExecuteProposal { proposal_id: 1 }
+                .try_to_vec()
+                .unwrap(),
             vec![
-                AccountMeta::new(user.pubkey(), true),
-                AccountMeta::new(codex_mint.pubkey(), false),
-                AccountMeta::new(ascendent_mint.pubkey(), false),
-                AccountMeta::new(mint_authority.pubkey(), true),
-                AccountMeta::new(ata, false),
+                AccountMeta::new(config_pda, false),
+                AccountMeta::new(proposal_pda, false),
+                AccountMeta::new_readonly(authority_pda, false),
+                AccountMeta::new_readonly(program_id, false),
+                AccountMeta::new_readonly(authority_pda, false),
+                AccountMeta::new(config_pda, false),
                 AccountMeta::new(payer.pubkey(), true),
-                AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
                 AccountMeta::new_readonly(system_program::id(), false),
-                AccountMeta::new_readonly(token_program_id(), false),
-                AccountMeta::new(ownership_pda, false),
-                AccountMeta::new(plant_counter_pda, false),
-                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             ],
         );
 
-        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
-        tx.sign(&[&payer, user, mint_authority], blockhash);
-        banks_client.process_transaction(tx).await.unwrap();
-    }
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[create_ix(&outsider)], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &outsider], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "Only approvers can propose"
+        );
 
-    #[tokio::test]
-    async fn test_primordial_relic_first_known_plant() {
-        println!("\n🧪 TEST: PrimordialRelic - First mint of known plant");
+        let mut tx =
+            Transaction::new_with_payer(&[create_ix(&approvers[0])], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &approvers[0]], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
 
-        let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
-        let (mut banks_client, payer, _) = program_test.start().await;
+        let mut tx =
+            Transaction::new_with_payer(std::slice::from_ref(&execute_ix), Some(&payer.pubkey()));
+        tx.sign(&[&payer], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "One approval is below the threshold"
+        );
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
-        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
-            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mut tx =
+            Transaction::new_with_payer(&[approve_ix(&approvers[1])], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &approvers[1]], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
 
-        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let mut tx =
+            Transaction::new_with_payer(std::slice::from_ref(&execute_ix), Some(&payer.pubkey()));
+        tx.sign(&[&payer], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "The timelock has not passed yet"
+        );
 
-        mint_card(
-            &mut banks_client,
-            &payer,
-            program_id,
-            &user,
-            &mint_authority,
-            "Rose",
-            false,
-            CardRarityInstruction::PrimordialRelic,
-            &primordial_mint,
-            &common_mint,
-            &rare_mint,
-            &epic_mint,
-            &aurora_mint,
-            &primordial_mint,
+        let proposal = Proposal::try_from_slice(
+            &context
+                .banks_client
+                .get_account(proposal_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
         )
-        .await;
+        .unwrap();
+        let ready_at = proposal.ready_at.unwrap();
+        warp_clock(&mut context, ready_at).await;
 
-        println!("✅ PrimordialRelic minted for first known plant mint");
+        // The executor cannot swap in accounts the approvers did not see.
+        let mut tampered_ix = execute_ix.clone();
+        tampered_ix.accounts[6] = AccountMeta::new(outsider.pubkey(), true);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[tampered_ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &outsider], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "Accounts must match the proposal"
+        );
+
+        let mut tx =
+            Transaction::new_with_payer(std::slice::from_ref(&execute_ix), Some(&payer.pubkey()));
+        tx.sign(&[&payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let config = ProgramConfig::try_from_slice(
+            &context
+                .banks_client
+                .get_account(config_pda)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        assert!(config.merkle_log);
+        assert_eq!(config.admin, authority_pda);
+
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[execute_ix], Some(&payer.pubkey()));
+        tx.sign(&[&payer], blockhash);
+        assert!(
+            context.banks_client.process_transaction(tx).await.is_err(),
+            "A proposal runs once"
+        );
+
+        println!("✅ 2-of-3 proposal executed after its timelock, exactly once");
     }
 
     #[tokio::test]
-    async fn test_aurora_seed_new_species() {
-        println!("\n🧪 TEST: AuroraSeed - First mint of NEW species");
+    async fn test_pause_blocks_minting() {
+        println!("\n🧪 TEST: A pauser can stop discovery minting and resume it");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
         let (mut banks_client, payer, _) = program_test.start().await;
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
-        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
-            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
-
-        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        let pauser = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &admin).await;
+        grant_role(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &admin,
+            &pauser.pubkey(),
+            Role::Pauser,
+        )
+        .await;
 
-        mint_card(
+        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
             &mut banks_client,
             &payer,
             program_id,
-            &user,
-            &mint_authority,
-            "NewSpecies",
-            true,
-            CardRarityInstruction::AuroraSeed,
-            &aurora_mint,
+            &admin,
+            &mint_authority.pubkey(),
+        )
+        .await;
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
+            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        let mints = [
             &common_mint,
             &rare_mint,
             &epic_mint,
             &aurora_mint,
             &primordial_mint,
+        ];
+        let user = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+
+        let pause_ix = |signer: &Keypair, instruction: ProgramInstruction| {
+            Instruction::new_with_bytes(
+                program_id,
+                &instruction.try_to_vec().unwrap(),
+                vec![
+                    AccountMeta::new_readonly(signer.pubkey(), true),
+                    AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                    AccountMeta::new(find_config_address(&program_id).0, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            )
+        };
+        let mint_ix = || {
+            mint_nft_ix(
+                program_id,
+                &payer,
+                &user,
+                &mint_authority,
+                "Rose",
+                false,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+                mints,
+                token_program_id(),
+            )
+        };
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[pause_ix(
+                &admin,
+                ProgramInstruction::Pause {
+                    scope: PauseScope::Discovery,
+                },
+            )],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &admin], blockhash);
+        assert!(
+            banks_client.process_transaction(tx).await.is_err(),
+            "Only pausers can pause"
+        );
+
+        let mut tx = Transaction::new_with_payer(
+            &[pause_ix(
+                &pauser,
+                ProgramInstruction::Pause {
+                    scope: PauseScope::Discovery,
+                },
+            )],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &pauser], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let mut tx = Transaction::new_with_payer(&[mint_ix()], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user, &mint_authority], blockhash);
+        assert!(
+            banks_client.process_transaction(tx).await.is_err(),
+            "Discovery mints fail while paused"
+        );
+        assert!(
+            submit_species_claim(&mut banks_client, &payer, program_id, &user, "Orchid")
+                .await
+                .is_err(),
+            "Species claims fail while discovery is paused"
+        );
+
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(
+            &[
+                pause_ix(
+                    &pauser,
+                    ProgramInstruction::Unpause {
+                        scope: PauseScope::Discovery,
+                    },
+                ),
+                mint_ix(),
+            ],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &pauser, &user, &mint_authority], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let mut tx = Transaction::new_with_payer(
+            &[pause_ix(
+                &pauser,
+                ProgramInstruction::Pause {
+                    scope: PauseScope::All,
+                },
+            )],
+            Some(&payer.pubkey()),
+        );
+        tx.sign(&[&payer, &pauser], blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let config = ProgramConfig::try_from_slice(
+            &banks_client
+                .get_account(find_config_address(&program_id).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
         )
-        .await;
+        .unwrap();
+        assert!(config.is_paused(PauseScope::Discovery));
+        assert!(config.is_paused(PauseScope::Quizzes));
 
-        println!("✅ AuroraSeed minted for new species discovery");
+        println!("✅ Discovery paused, resumed, then everything paused");
     }
 
     #[tokio::test]
-    async fn test_mythic_crest_regular_distribution() {
-        println!("\n🧪 TEST: MythicCrest - Regular distribution (2nd mint)");
+    async fn test_daily_mint_limit() {
+        println!("\n🧪 TEST: A wallet cannot mint more cards per day than the configured limit");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
-        let (mut banks_client, payer, _) = program_test.start().await;
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let admin = add_upgrade_authority(&mut program_test, program_id);
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
-        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
-            setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
+        initialize_config(&mut context.banks_client, &payer, program_id, &admin).await;
+        update_config(
+            &mut context.banks_client,
+            &payer,
+            program_id,
+            &admin,
+            ConfigUpdate::SetDailyMintLimit { limit: Some(2) },
+        )
+        .await
+        .unwrap();
 
-        // First user gets PrimordialRelic
-        let user1 = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
-        mint_card(
-            &mut banks_client,
+        let mint_authority =
+            create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
+        grant_minting_roles(
+            &mut context.banks_client,
             &payer,
             program_id,
-            &user1,
-            &mint_authority,
-            "Sunflower",
-            false,
-            CardRarityInstruction::PrimordialRelic,
-            &primordial_mint,
-            &common_mint,
-            &rare_mint,
-            &epic_mint,
-            &aurora_mint,
-            &primordial_mint,
+            &admin,
+            &mint_authority.pubkey(),
         )
         .await;
-        println!("  → User 1 got PrimordialRelic");
-
-        // Second user should get MythicCrest
-        let user2 = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
-        mint_card(
-            &mut banks_client,
+        let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) = setup_mints(
+            &mut context.banks_client,
             &payer,
             program_id,
-            &user2,
             &mint_authority,
-            "Sunflower",
-            false,
-            CardRarityInstruction::MythicCrest,
-            &epic_mint,
+        )
+        .await;
+        let mints = [
             &common_mint,
             &rare_mint,
             &epic_mint,
             &aurora_mint,
             &primordial_mint,
-        )
-        .await;
+        ];
+        let user = create_funded_keypair(&mut context.banks_client, &payer, 1_000_000_000).await;
 
-        println!("✅ MythicCrest minted in regular distribution");
-    }
+        let mint_ix = |plant_name: &str| {
+            mint_nft_ix(
+                program_id,
+                &payer,
+                &user,
+                &mint_authority,
+                plant_name,
+                false,
+                CardRarityInstruction::PrimordialRelic,
+                &primordial_mint,
+                mints,
+                token_program_id(),
+            )
+        };
 
-    #[tokio::test]
-    async fn test_mastery_card() {
-        println!("\nTesting Ascendental Seed");
-        let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
-        let (mut bank_client, payer, _) = program_test.start().await;
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        for plant_name in ["Rose", "Lotus"] {
+            let mut tx = Transaction::new_with_payer(&[mint_ix(plant_name)], Some(&payer.pubkey()));
+            tx.sign(&[&payer, &user, &mint_authority], blockhash);
+            context.banks_client.process_transaction(tx).await.unwrap();
+        }
 
-        let mint_authority = create_funded_keypair(&mut bank_client, &payer, 1_000_000_000).await;
-        let (codex_mint, ascendent_mint) =
-            setup_quiz_mints(&mut bank_client, &payer, program_id, &mint_authority).await;
+        let mut tx = Transaction::new_with_payer(&[mint_ix("Fern")], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user, &mint_authority], blockhash);
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(BiodexError::MintRateLimited as u32),
+            )
+        );
 
-        let user = create_funded_keypair(&mut bank_client, &payer, 1_000_000_000).await;
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+        warp_clock(&mut context, now + RATE_LIMIT_WINDOW).await;
 
-        let plant_name = "Orange";
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_with_payer(&[mint_ix("Fern")], Some(&payer.pubkey()));
+        tx.sign(&[&payer, &user, &mint_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
 
-        println!("\nMinting AscendantSeal\n");
-        mint_quiz_card(
-            &mut bank_client,
-            &payer,
-            program_id,
-            &user,
-            &mint_authority,
-            plant_name,
-            true,
-            CardRarityInstruction::AscendantSeal,
-            &ascendent_mint,
-            &codex_mint,
-            &ascendent_mint,
+        let rate_limit = MintRateLimit::try_from_slice(
+            &context
+                .banks_client
+                .get_account(find_rate_limit_address(&program_id, &user.pubkey()).0)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
         )
-        .await;
-    }
-
-    #[tokio::test]
-    async fn test_codex_card() {
-        println!("\nTesting Ascendental Seed");
-        let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
-        let (mut bank_client, payer, _) = program_test.start().await;
-
-        let mint_authority = create_funded_keypair(&mut bank_client, &payer, 1_000_000_000).await;
-        let (codex_mint, ascendent_mint) =
-            setup_quiz_mints(&mut bank_client, &payer, program_id, &mint_authority).await;
+        .unwrap();
+        assert_eq!(rate_limit.recent_mints, vec![now + RATE_LIMIT_WINDOW]);
 
-        let user = create_funded_keypair(&mut bank_client, &payer, 1_000_000_000).await;
-
-        let plant_name = "Orange";
-
-        println!("\nMinting CodexOfInsight\n");
-        mint_quiz_card(
-            &mut bank_client,
-            &payer,
-            program_id,
-            &user,
-            &mint_authority,
-            plant_name,
-            false,
-            CardRarityInstruction::CodexOfInsight,
-            &codex_mint,
-            &codex_mint,
-            &ascendent_mint,
-        )
-        .await;
+        println!("✅ Third mint rejected until the day had passed");
     }
 
     #[tokio::test]
@@ -462,10 +5900,12 @@ mod tests {
         println!("\n🧪 TEST: AstralShard - After MythicCrest slots exhausted");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
         let (mut banks_client, payer, _) = program_test.start().await;
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
         let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
             setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
 
@@ -543,10 +5983,12 @@ mod tests {
         println!("\n🧪 TEST: GenesisFragment - After all rare slots exhausted");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
         let (mut banks_client, payer, _) = program_test.start().await;
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
         let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
             setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
 
@@ -647,10 +6089,12 @@ mod tests {
         println!("\n🧪 TEST: Duplicate prevention - Same rarity for same plant");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
         let (mut banks_client, payer, _) = program_test.start().await;
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
         let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
             setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
 
@@ -696,7 +6140,7 @@ mod tests {
                 card_type: CardRarityInstruction::GenesisFragment,
                 plant_name: "Orchid".to_string(),
                 is_new_species: Some(false),
-                quiz_winner: Some(false),
+                context: None,
             }
             .try_to_vec()
             .unwrap(),
@@ -715,6 +6159,22 @@ mod tests {
                 AccountMeta::new_readonly(token_program_id(), false),
                 AccountMeta::new(ownership_pda, false),
                 AccountMeta::new(plant_counter_pda, false),
+                AccountMeta::new_readonly(find_config_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_user_profile_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new(find_plant_registry_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_collection_page_address(&program_id, &user.pubkey(), 0).0,
+                    false,
+                ),
+                AccountMeta::new_readonly(find_role_registry_address(&program_id).0, false),
+                AccountMeta::new(
+                    find_rate_limit_address(&program_id, &user.pubkey()).0,
+                    false,
+                ),
+                AccountMeta::new_readonly(find_mint_authority_address(&program_id).0, false),
                 AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             ],
         );
@@ -734,10 +6194,12 @@ mod tests {
         println!("\n🧪 TEST: User collects MULTIPLE different rarities for SAME plant");
 
         let program_id = Pubkey::new_unique();
-        let program_test = ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mut program_test =
+            ProgramTest::new("program", program_id, processor!(process_instruction));
+        let mint_authority = add_upgrade_authority(&mut program_test, program_id);
         let (mut banks_client, payer, _) = program_test.start().await;
 
-        let mint_authority = create_funded_keypair(&mut banks_client, &payer, 1_000_000_000).await;
+        initialize_config(&mut banks_client, &payer, program_id, &mint_authority).await;
         let (common_mint, rare_mint, epic_mint, aurora_mint, primordial_mint) =
             setup_mints(&mut banks_client, &payer, program_id, &mint_authority).await;
 